@@ -0,0 +1,76 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Invalid puzzle input, located by 1-based line and column.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, empty if something is missing.
+    pub text: String,
+    /// Description of what should have been there instead.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Reports `text`, which must be a slice of `input`, at its position in
+    /// `input`.
+    pub fn new(input: &str, text: &str, expected: impl Into<String>) -> Self {
+        let offset = (text.as_ptr() as usize)
+            .wrapping_sub(input.as_ptr() as usize)
+            .min(input.len());
+        debug_assert!(offset + text.len() <= input.len(), "text not within input");
+        let before = &input[..offset];
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before.chars().rev().take_while(|&c| c != '\n').count() + 1,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Reports that `input` ended where `expected` should have been.
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        Self::new(input, &input[input.len()..], expected)
+    }
+
+    /// Relocates an error reported against `text` to its position in `input`,
+    /// `text` being a slice of `input`.
+    pub fn within(self, input: &str, text: &str) -> Self {
+        let origin = Self::new(input, text, "");
+        Self {
+            line: origin.line + self.line - 1,
+            column: if self.line == 1 {
+                origin.column + self.column - 1
+            } else {
+                self.column
+            },
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.text.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "'{}'", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `text`, a slice of `input`, reporting an error at its position if it
+/// isn't a valid `T`.
+pub fn parse<T: FromStr>(input: &str, text: &str, expected: &str) -> Result<T, ParseError> {
+    text.parse::<T>()
+        .map_err(|_| ParseError::new(input, text, expected))
+}
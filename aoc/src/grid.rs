@@ -0,0 +1,177 @@
+use crate::{ParseError, Point};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Rectangular, row-major grid addressed by `Point`s, where `x` is the column
+/// and `y` the row. Out of bounds accesses yield `None` rather than wrapping.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses one row per line of `s`, mapping every character to a tile.
+    /// `f` rejects a character by returning a description of the tiles it
+    /// expected instead.
+    pub fn parse(
+        s: &str,
+        mut f: impl FnMut(char) -> Result<T, &'static str>,
+    ) -> Result<Self, ParseError> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for line in s.lines() {
+            let row = line
+                .char_indices()
+                .map(|(i, c)| {
+                    f(c).map_err(|expected| {
+                        ParseError::new(s, &line[i..i + c.len_utf8()], expected)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    return Err(ParseError::new(
+                        s,
+                        line,
+                        format!("a row of {} tiles", first.len()),
+                    ));
+                }
+            }
+            rows.push(row);
+        }
+        Ok(Self::from_rows(rows))
+    }
+
+    /// Builds a grid from its rows. Panics if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "grid rows must all have the same length"
+        );
+        Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && (p.x as usize) < self.width && p.y >= 0 && (p.y as usize) < self.height
+    }
+
+    fn offset(&self, p: Point) -> Option<usize> {
+        if self.contains(p) {
+            Some(p.y as usize * self.width + p.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    /// All positions of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |y| (0..width).map(move |x| Point::new(x as isize, y as isize)))
+    }
+
+    /// In bounds positions orthogonally adjacent to `p`.
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Point::NEIGHBORS4
+            .into_iter()
+            .map(move |delta| p + delta)
+            .filter(|&n| self.contains(n))
+    }
+
+    /// In bounds positions surrounding `p`, diagonals included.
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Point::NEIGHBORS8
+            .into_iter()
+            .map(move |delta| p + delta)
+            .filter(|&n| self.contains(n))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.points().find(|&p| predicate(&self[p]))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn find(&self, tile: &T) -> Option<Point> {
+        self.position(|t| t == tile)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} out of grid bounds", p))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{:?} out of grid bounds", p))
+    }
+}
+
+/// Prints one line per row, so a grid parsed from puzzle text displays as
+/// that same text.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for tile in row {
+                write!(f, "{}", tile)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+mod direction;
+mod error;
+mod grid;
+mod params;
+mod point;
+mod rng;
+mod solution;
+
+pub use direction::Direction;
+pub use error::{parse, ParseError};
+pub use grid::Grid;
+pub use params::{Param, Params};
+pub use point::Point;
+pub use rng::Rng;
+pub use solution::{solve, Answer, Answers, Part, Solution, SolveError, Timings};
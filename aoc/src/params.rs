@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+/// A puzzle constant that can be changed with `--name value`, e.g. to solve
+/// a puzzle example on a smaller map than the real input's.
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value used by the real puzzle.
+    pub default: u64,
+    pub description: &'static str,
+}
+
+/// The values of a day's `Param`s.
+#[derive(Clone, Debug, Default)]
+pub struct Params {
+    values: HashMap<&'static str, u64>,
+}
+
+impl Params {
+    /// The default values of `params`.
+    pub fn new(params: &'static [Param]) -> Self {
+        Self {
+            values: params
+                .iter()
+                .map(|param| (param.name, param.default))
+                .collect(),
+        }
+    }
+
+    /// Fails if there is no parameter called `name`.
+    pub fn set(&mut self, name: &str, value: u64) -> Result<(), String> {
+        let value_mut = self
+            .values
+            .get_mut(name)
+            .ok_or(format!("unknown parameter: {}", name))?;
+        *value_mut = value;
+        Ok(())
+    }
+
+    /// Panics if there is no parameter called `name`.
+    pub fn get(&self, name: &str) -> u64 {
+        *self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("unknown parameter: {}", name))
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{parse, Direction, ParseError};
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    /// Offsets to the four orthogonally adjacent positions.
+    pub const NEIGHBORS4: [Point; 4] = [
+        Point::new(0, -1),
+        Point::new(-1, 0),
+        Point::new(1, 0),
+        Point::new(0, 1),
+    ];
+
+    /// Offsets to the eight surrounding positions, diagonals included.
+    pub const NEIGHBORS8: [Point; 8] = [
+        Point::new(-1, -1),
+        Point::new(0, -1),
+        Point::new(1, -1),
+        Point::new(-1, 0),
+        Point::new(1, 0),
+        Point::new(-1, 1),
+        Point::new(0, 1),
+        Point::new(1, 1),
+    ];
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn next_position(&self, direction: Direction) -> Self {
+        *self + direction.delta()
+    }
+
+    /// Manhattan distance between the two points.
+    pub fn distance(&self, other: &Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Parses the `x,y` notation used by the puzzle inputs.
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::new(s, s, "a point 'x,y'"))?;
+        Ok(Point {
+            x: parse(s, x, "an integer")?,
+            y: parse(s, y, "an integer")?,
+        })
+    }
+}
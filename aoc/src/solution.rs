@@ -0,0 +1,135 @@
+use crate::{Param, Params, ParseError};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+/// What a part returns: `Option<T>` for parts that always finish, or
+/// `Result<Option<T>, E>` for parts that can fail on some inputs.
+pub trait Answer {
+    fn into_answer(self) -> Result<Option<String>, Box<dyn Error>>;
+}
+
+impl<T: Display> Answer for Option<T> {
+    fn into_answer(self) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(self.map(|answer| answer.to_string()))
+    }
+}
+
+impl<T: Display, E: Error + 'static> Answer for Result<Option<T>, E> {
+    fn into_answer(self) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(self?.map(|answer| answer.to_string()))
+    }
+}
+
+/// A day's puzzle. `parse` turns the raw puzzle input into the value both
+/// parts are computed from. A part returns `None` when the input has no
+/// answer for it, and an error when it can't tell.
+pub trait Solution: Sized {
+    /// The puzzle constants `parse_with` reads from its `Params`.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self, ParseError>;
+
+    /// Like `parse`, with the values of `PARAMS` taken from `params` instead
+    /// of the puzzle's.
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let _ = params;
+        Self::parse(input)
+    }
+
+    fn part1(&self) -> impl Answer;
+    fn part2(&self) -> impl Answer;
+
+    /// Anything worth showing besides the answers, like day 14's picture,
+    /// once the parts have run. It isn't timed.
+    fn diagnostics(&self) -> Option<String> {
+        None
+    }
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// Why `solve` has no answers.
+#[derive(Debug)]
+pub enum SolveError {
+    Parse(ParseError),
+    /// A part failed on the input.
+    Part(Part, Box<dyn Error>),
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> Self {
+        SolveError::Parse(e)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Parse(e) => write!(f, "{}", e),
+            SolveError::Part(Part::One, e) => write!(f, "part 1: {}", e),
+            SolveError::Part(Part::Two, e) => write!(f, "part 2: {}", e),
+        }
+    }
+}
+
+impl Error for SolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolveError::Parse(e) => Some(e),
+            SolveError::Part(_, e) => Some(e.as_ref()),
+        }
+    }
+}
+
+/// Wall-clock time spent in each phase, `None` for the parts that weren't run.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+#[derive(Default, Debug)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub timings: Timings,
+    /// What the solution had to show besides its answers.
+    pub diagnostics: Option<String>,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Parses `input` with `params` and computes the requested part, or both
+/// parts if `part` is `None`. Stops at the first part that fails.
+pub fn solve<S: Solution>(
+    input: &str,
+    params: &Params,
+    part: Option<Part>,
+) -> Result<Answers, SolveError> {
+    let (solution, parse_time) = timed(|| S::parse_with(input, params));
+    let solution = solution?;
+    let mut answers = Answers::default();
+    answers.timings.parse = parse_time;
+    if part != Some(Part::Two) {
+        let (answer, time) = timed(|| solution.part1().into_answer());
+        answers.part1 = answer.map_err(|e| SolveError::Part(Part::One, e))?;
+        answers.timings.part1 = Some(time);
+    }
+    if part != Some(Part::One) {
+        let (answer, time) = timed(|| solution.part2().into_answer());
+        answers.part2 = answer.map_err(|e| SolveError::Part(Part::Two, e))?;
+        answers.timings.part2 = Some(time);
+    }
+    answers.diagnostics = solution.diagnostics();
+    Ok(answers)
+}
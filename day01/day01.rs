@@ -1,41 +1,55 @@
+use aoc::{parse, Answer, ParseError, Solution};
 use std::collections::HashMap;
-use std::io;
-
-fn main() {
-    let mut left = Vec::new();
-    let mut right = Vec::new();
-    let mut right_count = HashMap::new();
-
-    for line in io::stdin().lines() {
-        let line = line.expect("error reading input");
-        let (lstr, rstr) = line.split_once("   ").expect("error parsing input");
-        let (l, r) = (
-            lstr.parse::<u32>().expect("expected integer"),
-            rstr.parse::<u32>().expect("expected integer"),
-        );
-        left.push(l);
-        right.push(r);
-
-        right_count
-            .entry(r)
-            .and_modify(|count| *count += 1)
-            .or_insert(1);
+
+pub struct Day01 {
+    left: Vec<u32>,
+    right: Vec<u32>,
+}
+
+impl Solution for Day01 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        for line in input.lines() {
+            let (lstr, rstr) = line
+                .split_once("   ")
+                .ok_or_else(|| ParseError::new(input, line, "two location IDs"))?;
+            left.push(parse(input, lstr, "an integer")?);
+            right.push(parse(input, rstr, "an integer")?);
+        }
+
+        Ok(Self { left, right })
     }
 
-    left.sort();
-    right.sort();
-
-    println!(
-        "part 1: {}",
-        left.iter()
-            .zip(right.iter())
-            .map(|(l, r)| l.abs_diff(*r))
-            .sum::<u32>()
-    );
-    println!(
-        "part 2: {}",
-        left.iter()
-            .map(|l| *l * right_count.get(l).unwrap_or(&0))
-            .sum::<u32>()
-    );
+    fn part1(&self) -> impl Answer {
+        let mut left = self.left.clone();
+        let mut right = self.right.clone();
+        left.sort();
+        right.sort();
+
+        Some(
+            left.iter()
+                .zip(right.iter())
+                .map(|(l, r)| l.abs_diff(*r))
+                .sum::<u32>(),
+        )
+    }
+
+    fn part2(&self) -> impl Answer {
+        let mut right_count = HashMap::new();
+        for &r in &self.right {
+            right_count
+                .entry(r)
+                .and_modify(|count| *count += 1)
+                .or_insert(1);
+        }
+
+        Some(
+            self.left
+                .iter()
+                .map(|l| *l * right_count.get(l).unwrap_or(&0))
+                .sum::<u32>(),
+        )
+    }
 }
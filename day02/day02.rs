@@ -1,4 +1,4 @@
-use std::io;
+use aoc::{parse, Answer, ParseError, Solution};
 
 fn check(report: &[i32], i: usize, expected: i32, bad: usize, max_bad: usize) -> bool {
     if bad > max_bad {
@@ -17,7 +17,7 @@ fn check(report: &[i32], i: usize, expected: i32, bad: usize, max_bad: usize) ->
 }
 
 fn safe(report: &[i32], max_bad: usize) -> bool {
-    let reversed: Vec<_> = report.iter().rev().map(|x| *x).collect();
+    let reversed: Vec<_> = report.iter().rev().copied().collect();
 
     check(report, 0, report[0], 0, max_bad)
         || check(&reversed, 0, reversed[0], 0, max_bad)
@@ -25,23 +25,28 @@ fn safe(report: &[i32], max_bad: usize) -> bool {
         || check(&reversed, 1, reversed[1], 1, max_bad)
 }
 
-fn main() {
-    let reports: Vec<Vec<i32>> = io::stdin()
-        .lines()
-        .map(|line| {
-            let line = line.expect("error reading input");
-            line.split_whitespace()
-                .map(|level| level.parse::<i32>().expect("error parsing level"))
-                .collect()
-        })
-        .collect();
+pub struct Day02 {
+    reports: Vec<Vec<i32>>,
+}
+
+impl Solution for Day02 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let reports = input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|level| parse(input, level, "a level"))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { reports })
+    }
 
-    println!(
-        "part 1: {}",
-        reports.iter().filter(|report| safe(report, 0)).count()
-    );
-    println!(
-        "part 2: {}",
-        reports.iter().filter(|report| safe(report, 1)).count()
-    );
+    fn part1(&self) -> impl Answer {
+        Some(self.reports.iter().filter(|report| safe(report, 0)).count())
+    }
+
+    fn part2(&self) -> impl Answer {
+        Some(self.reports.iter().filter(|report| safe(report, 1)).count())
+    }
 }
@@ -1,4 +1,4 @@
-use std::io;
+use aoc::{Answer, ParseError, Solution};
 
 enum PatternNode {
     Str(String),
@@ -58,52 +58,68 @@ fn parse_and_compute_mul(s: &str, mul: &Match) -> u32 {
     x.parse::<u32>().unwrap() * y.parse::<u32>().unwrap()
 }
 
-fn main() {
-    let memory: Vec<String> = io::stdin()
-        .lines()
-        .map(|line| line.expect("error reading input"))
-        .collect();
-
-    let mul_pattern = [
+fn mul_pattern() -> [PatternNode; 5] {
+    [
         PatternNode::Str(String::from("mul(")),
         PatternNode::Int,
         PatternNode::Str(String::from(",")),
         PatternNode::Int,
         PatternNode::Str(String::from(")")),
-    ];
+    ]
+}
 
-    let mut part1: u32 = 0;
-    let mut part2: u32 = 0;
-    let mut enabled = true;
+pub struct Day03 {
+    memory: Vec<String>,
+}
 
-    for s in memory {
-        let mut multiplications = find_matches(&s, &mul_pattern);
-        part1 += multiplications
-            .iter()
-            .map(|m| parse_and_compute_mul(&s, m))
-            .sum::<u32>();
+impl Solution for Day03 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            memory: input.lines().map(String::from).collect(),
+        })
+    }
 
-        let mut dos = find_matches(&s, &[PatternNode::Str(String::from("do()"))]);
-        let mut donts = find_matches(&s, &[PatternNode::Str(String::from("don't()"))]);
+    fn part1(&self) -> impl Answer {
+        let mul_pattern = mul_pattern();
+        Some(
+            self.memory
+                .iter()
+                .map(|s| {
+                    find_matches(s, &mul_pattern)
+                        .iter()
+                        .map(|m| parse_and_compute_mul(s, m))
+                        .sum::<u32>()
+                })
+                .sum::<u32>(),
+        )
+    }
 
-        let mut instructions = Vec::new();
-        instructions.append(&mut multiplications);
-        instructions.append(&mut dos);
-        instructions.append(&mut donts);
-        instructions.sort_by_key(|m| m.start);
+    fn part2(&self) -> impl Answer {
+        let mul_pattern = mul_pattern();
+        let mut part2: u32 = 0;
+        let mut enabled = true;
 
-        for inst in instructions {
-            let inst_str = &s[inst.start..inst.end];
-            if inst_str.starts_with("mul") && enabled {
-                part2 += parse_and_compute_mul(&s, &inst);
-            } else if inst_str.starts_with("do()") {
-                enabled = true;
-            } else {
-                enabled = false;
+        for s in &self.memory {
+            let mut multiplications = find_matches(s, &mul_pattern);
+            let mut dos = find_matches(s, &[PatternNode::Str(String::from("do()"))]);
+            let mut donts = find_matches(s, &[PatternNode::Str(String::from("don't()"))]);
+
+            let mut instructions = Vec::new();
+            instructions.append(&mut multiplications);
+            instructions.append(&mut dos);
+            instructions.append(&mut donts);
+            instructions.sort_by_key(|m| m.start);
+
+            for inst in instructions {
+                let inst_str = &s[inst.start..inst.end];
+                if inst_str.starts_with("mul") && enabled {
+                    part2 += parse_and_compute_mul(s, &inst);
+                } else {
+                    enabled = inst_str.starts_with("do()");
+                }
             }
         }
-    }
 
-    println!("part 1: {}", part1);
-    println!("part 2: {}", part2);
+        Some(part2)
+    }
 }
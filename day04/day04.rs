@@ -1,71 +1,49 @@
-use std::io;
+use aoc::{Answer, Grid, ParseError, Point, Solution};
 
-fn find_word(
-    word: &str,
-    grid: &[Vec<char>],
-    line: usize,
-    col: usize,
-    direction: &(isize, isize),
-) -> bool {
-    for (i, c) in word.chars().enumerate() {
-        let nline = line as isize + direction.0 * (i as isize);
-        let ncol = col as isize + direction.1 * (i as isize);
-        if nline < 0
-            || (nline as usize) >= grid.len()
-            || ncol < 0
-            || (ncol as usize) >= grid[nline as usize].len()
-            || grid[nline as usize][ncol as usize] != c
-        {
-            return false;
-        }
-    }
-
-    true
+fn find_word(word: &str, grid: &Grid<char>, start: Point, direction: Point) -> bool {
+    word.chars()
+        .enumerate()
+        .all(|(i, c)| grid.get(start + direction * i as isize) == Some(&c))
 }
 
-fn is_x_mass(a: char, b: char) -> bool {
-    (a == 'M' || a == 'S') && (b == 'M' || b == 'S') && a != b
+fn is_x_mass(a: Option<&char>, b: Option<&char>) -> bool {
+    matches!((a, b), (Some('M'), Some('S')) | (Some('S'), Some('M')))
 }
 
-fn main() {
-    let grid: Vec<Vec<char>> = io::stdin()
-        .lines()
-        .map(|line| line.expect("error reading input").chars().collect())
-        .collect();
+pub struct Day04 {
+    grid: Grid<char>,
+}
 
-    let directions = [
-        (-1, -1),
-        (-1, 0),
-        (-1, 1),
-        (0, -1),
-        (0, 1),
-        (1, -1),
-        (1, 0),
-        (1, 1),
-    ];
+impl Solution for Day04 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            grid: Grid::parse(input, Ok)?,
+        })
+    }
 
-    let mut count = 0;
-    for i in 0..grid.len() {
-        for j in 0..grid[i].len() {
-            for dir in directions {
-                if find_word("XMAS", &grid, i, j, &dir) {
+    fn part1(&self) -> impl Answer {
+        let grid = &self.grid;
+        let mut count = 0;
+        for p in grid.points() {
+            for direction in Point::NEIGHBORS8 {
+                if find_word("XMAS", grid, p, direction) {
                     count += 1;
                 }
             }
         }
+        Some(count)
     }
-    println!("part 1: {}", count);
 
-    count = 0;
-    for i in 1..grid.len() - 1 {
-        for j in 1..grid[i].len() - 1 {
-            if grid[i][j] == 'A'
-                && is_x_mass(grid[i - 1][j - 1], grid[i + 1][j + 1])
-                && is_x_mass(grid[i + 1][j - 1], grid[i - 1][j + 1])
+    fn part2(&self) -> impl Answer {
+        let grid = &self.grid;
+        let mut count = 0;
+        for p in grid.points() {
+            let at = |dx, dy| grid.get(p + Point::new(dx, dy));
+            if grid[p] == 'A' && is_x_mass(at(-1, -1), at(1, 1)) && is_x_mass(at(1, -1), at(-1, 1))
             {
                 count += 1;
             }
         }
+        Some(count)
     }
-    println!("part 2: {}", count);
 }
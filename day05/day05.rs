@@ -1,7 +1,7 @@
+use aoc::{parse, Answer, ParseError, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::io;
 
 fn correct_order(update: &[usize], dependencies: &HashMap<usize, HashSet<usize>>) -> Vec<usize> {
     let update_set: HashSet<usize> = update.iter().cloned().collect();
@@ -18,7 +18,7 @@ fn correct_order(update: &[usize], dependencies: &HashMap<usize, HashSet<usize>>
             == 0
         {
             print_order.push(page);
-            for (_, deps) in &mut dependencies {
+            for deps in dependencies.values_mut() {
                 deps.remove(&page);
             }
         } else {
@@ -33,54 +33,71 @@ fn middle_page(update: &[usize]) -> usize {
     update[update.len() / 2]
 }
 
-fn main() {
-    let input = io::read_to_string(io::stdin()).expect("error reading input");
-    let (ordering_rules, updates) = input.split_once("\n\n").expect("error parsing input");
-    let ordering_rules: Vec<(usize, usize)> = ordering_rules
-        .lines()
-        .map(|line| line.split_once('|').expect("error parsing ordering rule"))
-        .map(|(x, y)| {
-            (
-                x.parse::<usize>().expect("error parsing page number"),
-                y.parse::<usize>().expect("error parsing page number"),
-            )
-        })
-        .collect();
-    let updates: Vec<Vec<usize>> = updates
-        .lines()
-        .map(|line| {
-            line.split(',')
-                .map(|page| page.parse::<usize>().expect("error parsing page number"))
-                .collect()
-        })
-        .collect();
+pub struct Day05 {
+    updates: Vec<Vec<usize>>,
+    dependencies: HashMap<usize, HashSet<usize>>,
+}
 
-    let mut dependencies: HashMap<usize, HashSet<usize>> = HashMap::new();
-    for (x, y) in ordering_rules {
-        dependencies.entry(y).or_insert(HashSet::new()).insert(x);
+impl Day05 {
+    fn correctly_ordered_updates(&self) -> impl Iterator<Item = (&Vec<usize>, Vec<usize>)> {
+        self.updates
+            .iter()
+            .map(|update| (update, correct_order(update, &self.dependencies)))
     }
+}
 
-    let correctly_ordered_updates: Vec<_> = updates
-        .iter()
-        .map(|update| correct_order(update, &dependencies))
-        .collect();
+impl Solution for Day05 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let (ordering_rules, updates) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::end_of_input(input, "a blank line followed by the updates")
+        })?;
+        let ordering_rules = ordering_rules
+            .lines()
+            .map(|line| {
+                let (x, y) = line
+                    .split_once('|')
+                    .ok_or_else(|| ParseError::new(input, line, "an ordering rule 'X|Y'"))?;
+                Ok((
+                    parse::<usize>(input, x, "a page number")?,
+                    parse::<usize>(input, y, "a page number")?,
+                ))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        let updates = updates
+            .lines()
+            .map(|line| {
+                line.split(',')
+                    .map(|page| parse(input, page, "a page number"))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
 
-    println!(
-        "part 1: {}",
-        updates
-            .iter()
-            .zip(correctly_ordered_updates.iter())
-            .filter(|(update, correctly_ordered_update)| update == correctly_ordered_update)
-            .map(|(update, _)| middle_page(update))
-            .sum::<usize>()
-    );
-    println!(
-        "part 2: {}",
-        updates
-            .iter()
-            .zip(correctly_ordered_updates.iter())
-            .filter(|(update, correctly_ordered_update)| update != correctly_ordered_update)
-            .map(|(_, correctly_ordered_update)| middle_page(correctly_ordered_update))
-            .sum::<usize>()
-    );
+        let mut dependencies: HashMap<usize, HashSet<usize>> = HashMap::new();
+        for (x, y) in ordering_rules {
+            dependencies.entry(y).or_default().insert(x);
+        }
+
+        Ok(Self {
+            updates,
+            dependencies,
+        })
+    }
+
+    fn part1(&self) -> impl Answer {
+        Some(
+            self.correctly_ordered_updates()
+                .filter(|(update, correctly_ordered_update)| *update == correctly_ordered_update)
+                .map(|(update, _)| middle_page(update))
+                .sum::<usize>(),
+        )
+    }
+
+    fn part2(&self) -> impl Answer {
+        Some(
+            self.correctly_ordered_updates()
+                .filter(|(update, correctly_ordered_update)| *update != correctly_ordered_update)
+                .map(|(_, correctly_ordered_update)| middle_page(&correctly_ordered_update))
+                .sum::<usize>(),
+        )
+    }
 }
@@ -1,5 +1,5 @@
+use aoc::{Answer, Direction, Grid, ParseError, Point, Solution};
 use std::collections::HashSet;
-use std::io;
 
 #[derive(PartialEq, Copy, Clone, Debug)]
 enum Tile {
@@ -7,43 +7,6 @@ enum Tile {
     Obstruction,
 }
 
-#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn turn_right(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-            Direction::Right => Direction::Down,
-        }
-    }
-}
-
-fn get_tile(pos: &Point, map: &[Vec<Tile>]) -> Option<Tile> {
-    if pos.y >= 0
-        && (pos.y as usize) < map.len()
-        && pos.x >= 0
-        && (pos.x as usize) < map[pos.y as usize].len()
-    {
-        Some(map[pos.y as usize][pos.x as usize])
-    } else {
-        None
-    }
-}
-
 #[derive(Eq, Hash, PartialEq, Copy, Clone, Debug)]
 struct Guard {
     pos: Point,
@@ -52,28 +15,11 @@ struct Guard {
 
 impl Guard {
     fn next_pos(&self) -> Point {
-        match self.dir {
-            Direction::Up => Point {
-                x: self.pos.x,
-                y: self.pos.y - 1,
-            },
-            Direction::Down => Point {
-                x: self.pos.x,
-                y: self.pos.y + 1,
-            },
-            Direction::Left => Point {
-                x: self.pos.x - 1,
-                y: self.pos.y,
-            },
-            Direction::Right => Point {
-                x: self.pos.x + 1,
-                y: self.pos.y,
-            },
-        }
+        self.pos.next_position(self.dir)
     }
 }
 
-fn find_loop(guard: &Guard, map: &[Vec<Tile>], guard_states: &mut HashSet<Guard>) -> bool {
+fn find_loop(guard: &Guard, map: &Grid<Tile>, guard_states: &mut HashSet<Guard>) -> bool {
     if guard_states.contains(guard) {
         return true;
     }
@@ -81,7 +27,7 @@ fn find_loop(guard: &Guard, map: &[Vec<Tile>], guard_states: &mut HashSet<Guard>
     guard_states.insert(*guard);
 
     let next_pos = guard.next_pos();
-    let ret = match get_tile(&next_pos, &map) {
+    let ret = match map.get(next_pos) {
         Some(Tile::Space) => find_loop(
             &Guard {
                 pos: next_pos,
@@ -106,74 +52,89 @@ fn find_loop(guard: &Guard, map: &[Vec<Tile>], guard_states: &mut HashSet<Guard>
     ret
 }
 
-fn main() {
-    let mut guard = Guard {
-        pos: Point { x: 0, y: 0 },
-        dir: Direction::Up,
-    };
-    let mut map: Vec<Vec<Tile>> = io::stdin()
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.expect("error reading input")
-                .chars()
-                .enumerate()
-                .map(|(x, c)| match c {
-                    '.' => Tile::Space,
-                    '#' => Tile::Obstruction,
-                    _ => {
-                        guard = Guard {
-                            pos: Point {
-                                x: x as isize,
-                                y: y as isize,
-                            },
-                            dir: match c {
-                                '^' => Direction::Up,
-                                'v' => Direction::Down,
-                                '<' => Direction::Left,
-                                '>' => Direction::Right,
-                                _ => panic!("invalid character in map"),
+pub struct Day06 {
+    map: Grid<Tile>,
+    guard: Guard,
+}
+
+impl Solution for Day06 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let chars = Grid::parse(input, |c| match c {
+            '.' | '#' | '^' | 'v' | '<' | '>' => Ok(c),
+            _ => Err("'.', '#' or a guard"),
+        })?;
+        let pos = chars
+            .position(|c| matches!(c, '^' | 'v' | '<' | '>'))
+            .ok_or_else(|| ParseError::end_of_input(input, "a guard"))?;
+        let guard = Guard {
+            pos,
+            dir: match chars[pos] {
+                '^' => Direction::Up,
+                'v' => Direction::Down,
+                '<' => Direction::Left,
+                _ => Direction::Right,
+            },
+        };
+        let map = chars.map(|&c| {
+            if c == '#' {
+                Tile::Obstruction
+            } else {
+                Tile::Space
+            }
+        });
+        Ok(Self { map, guard })
+    }
+
+    fn part1(&self) -> impl Answer {
+        let mut guard = self.guard;
+        let mut visited = HashSet::new();
+        loop {
+            visited.insert(guard.pos);
+
+            let next_pos = guard.next_pos();
+            match self.map.get(next_pos) {
+                Some(Tile::Space) => guard.pos = next_pos,
+                Some(Tile::Obstruction) => guard.dir = guard.dir.turn_right(),
+                None => break,
+            }
+        }
+        Some(visited.len())
+    }
+
+    fn part2(&self) -> impl Answer {
+        let mut map = self.map.clone();
+        let mut guard = self.guard;
+        let mut guard_states = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut loops = 0;
+        loop {
+            guard_states.insert(guard);
+            visited.insert(guard.pos);
+
+            let next_pos = guard.next_pos();
+            match map.get(next_pos) {
+                Some(Tile::Space) => {
+                    map[next_pos] = Tile::Obstruction;
+                    if !visited.contains(&next_pos)
+                        && find_loop(
+                            &Guard {
+                                pos: guard.pos,
+                                dir: guard.dir.turn_right(),
                             },
-                        };
-                        Tile::Space
+                            &map,
+                            &mut guard_states,
+                        )
+                    {
+                        loops += 1;
                     }
-                })
-                .collect()
-        })
-        .collect();
-
-    let mut guard_states = HashSet::new();
-    let mut visited = HashSet::new();
-    let mut loops = 0;
-    loop {
-        guard_states.insert(guard);
-        visited.insert(guard.pos);
+                    map[next_pos] = Tile::Space;
 
-        let next_pos = guard.next_pos();
-        match get_tile(&next_pos, &map) {
-            Some(Tile::Space) => {
-                map[next_pos.y as usize][next_pos.x as usize] = Tile::Obstruction;
-                if !visited.contains(&next_pos)
-                    && find_loop(
-                        &Guard {
-                            pos: guard.pos,
-                            dir: guard.dir.turn_right(),
-                        },
-                        &map,
-                        &mut guard_states,
-                    )
-                {
-                    loops += 1;
+                    guard.pos = next_pos;
                 }
-                map[next_pos.y as usize][next_pos.x as usize] = Tile::Space;
-
-                guard.pos = next_pos;
+                Some(Tile::Obstruction) => guard.dir = guard.dir.turn_right(),
+                None => break,
             }
-            Some(Tile::Obstruction) => guard.dir = guard.dir.turn_right(),
-            None => break,
         }
+        Some(loops)
     }
-
-    println!("part 1: {}", visited.len());
-    println!("part 2: {}", loops);
 }
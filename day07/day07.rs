@@ -1,4 +1,4 @@
-use std::io;
+use aoc::{parse, Answer, ParseError, Solution};
 use std::str::FromStr;
 
 struct Equation {
@@ -6,18 +6,17 @@ struct Equation {
     operands: Vec<u64>,
 }
 
-#[derive(Debug)]
-struct ParseEquationError;
-
 impl FromStr for Equation {
-    type Err = ParseEquationError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (result, operands) = s.split_once(": ").ok_or(ParseEquationError)?;
-        let result = result.parse::<u64>().map_err(|_| ParseEquationError)?;
+        let (result, operands) = s
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new(s, s, "an equation 'result: operands'"))?;
+        let result = parse(s, result, "a test value")?;
         let operands: Result<Vec<_>, _> = operands
             .split(' ')
-            .map(|n| n.parse::<u64>().map_err(|_| ParseEquationError))
+            .map(|n| parse(s, n, "an operand"))
             .collect();
         Ok(Self {
             result,
@@ -46,7 +45,7 @@ fn concat(lhs: u64, rhs: u64) -> u64 {
 type Operator = fn(u64, u64) -> u64;
 
 fn solvable(current: u64, target: u64, operands: &[u64], operators: &[Operator]) -> bool {
-    if operands.len() == 0 {
+    if operands.is_empty() {
         current == target
     } else if current > target {
         false
@@ -63,35 +62,39 @@ impl Equation {
             self.operands[0],
             self.result,
             &self.operands[1..],
-            &operators,
+            operators,
         )
     }
 }
 
-fn main() {
-    let equations: Vec<_> = io::stdin()
-        .lines()
-        .map(|line| {
-            line.expect("error reading input")
-                .parse::<Equation>()
-                .expect("error parsing equation")
-        })
-        .collect();
+pub struct Day07 {
+    equations: Vec<Equation>,
+}
 
-    println!(
-        "part 1: {}",
-        equations
+impl Day07 {
+    fn calibration_result(&self, operators: &[Operator]) -> u64 {
+        self.equations
             .iter()
-            .filter(|eq| eq.solvable(&[add, mul]))
+            .filter(|eq| eq.solvable(operators))
             .map(|eq| eq.result)
             .sum::<u64>()
-    );
-    println!(
-        "part 2: {}",
-        equations
-            .iter()
-            .filter(|eq| eq.solvable(&[add, mul, concat]))
-            .map(|eq| eq.result)
-            .sum::<u64>()
-    );
+    }
+}
+
+impl Solution for Day07 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let equations = input
+            .lines()
+            .map(|line| line.parse::<Equation>().map_err(|e| e.within(input, line)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { equations })
+    }
+
+    fn part1(&self) -> impl Answer {
+        Some(self.calibration_result(&[add, mul]))
+    }
+
+    fn part2(&self) -> impl Answer {
+        Some(self.calibration_result(&[add, mul, concat]))
+    }
 }
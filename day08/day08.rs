@@ -1,77 +1,81 @@
+use aoc::{Answer, Grid, ParseError, Point, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::io;
 
-#[derive(Eq, Hash, PartialEq, Copy, Clone)]
-struct Point {
-    x: isize,
-    y: isize,
+pub struct Day08 {
+    map: Grid<char>,
 }
 
-fn within_bounds(p: &Point, map: &[Vec<char>]) -> bool {
-    p.y >= 0 && (p.y as usize) < map.len() && p.x >= 0 && (p.x as usize) < map[p.y as usize].len()
+impl Day08 {
+    fn antennas(&self) -> HashMap<char, Vec<Point>> {
+        let mut antennas = HashMap::new();
+        for p in self.map.points() {
+            if self.map[p] != '.' {
+                antennas.entry(self.map[p]).or_insert(Vec::new()).push(p);
+            }
+        }
+        antennas
+    }
 }
 
-fn main() {
-    let map: Vec<Vec<_>> = io::stdin()
-        .lines()
-        .map(|line| line.expect("error reading input").chars().collect())
-        .collect();
-    let mut antennas = HashMap::new();
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if map[y][x] != '.' {
-                antennas.entry(map[y][x]).or_insert(Vec::new()).push(Point {
-                    x: x as isize,
-                    y: y as isize,
-                });
+impl Solution for Day08 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            map: Grid::parse(input, Ok)?,
+        })
+    }
+
+    fn part1(&self) -> impl Answer {
+        let map = &self.map;
+        let mut antinodes = HashSet::new();
+
+        for (_, antennas) in self.antennas() {
+            for i in 0..antennas.len() - 1 {
+                for j in i + 1..antennas.len() {
+                    let (a, b) = (antennas[i], antennas[j]);
+                    let delta = a - b;
+
+                    let antinode = a + delta;
+                    if map.contains(antinode) {
+                        antinodes.insert(antinode);
+                    }
+
+                    let antinode = b - delta;
+                    if map.contains(antinode) {
+                        antinodes.insert(antinode);
+                    }
+                }
             }
         }
+
+        Some(antinodes.len())
     }
 
-    let mut antinodes = HashSet::new();
-    let mut antinodes_with_harmonics = HashSet::new();
+    fn part2(&self) -> impl Answer {
+        let map = &self.map;
+        let mut antinodes_with_harmonics = HashSet::new();
 
-    for (_, antennas) in antennas {
-        for i in 0..antennas.len() - 1 {
-            for j in i + 1..antennas.len() {
-                let (a, b) = (antennas[i], antennas[j]);
-                let delta = Point {
-                    x: a.x - b.x,
-                    y: a.y - b.y,
-                };
+        for (_, antennas) in self.antennas() {
+            for i in 0..antennas.len() - 1 {
+                for j in i + 1..antennas.len() {
+                    let (a, b) = (antennas[i], antennas[j]);
+                    let delta = a - b;
 
-                let antinode = Point {
-                    x: a.x + delta.x,
-                    y: a.y + delta.y,
-                };
-                if within_bounds(&antinode, &map) {
-                    antinodes.insert(antinode);
-                }
-                let mut antinode = a;
-                while within_bounds(&antinode, &map) {
-                    antinodes_with_harmonics.insert(antinode);
-                    antinode.x += delta.x;
-                    antinode.y += delta.y;
-                }
+                    let mut antinode = a;
+                    while map.contains(antinode) {
+                        antinodes_with_harmonics.insert(antinode);
+                        antinode += delta;
+                    }
 
-                let antinode = Point {
-                    x: b.x - delta.x,
-                    y: b.y - delta.y,
-                };
-                if within_bounds(&antinode, &map) {
-                    antinodes.insert(antinode);
-                }
-                let mut antinode = b;
-                while within_bounds(&antinode, &map) {
-                    antinodes_with_harmonics.insert(antinode);
-                    antinode.x -= delta.x;
-                    antinode.y -= delta.y;
+                    let mut antinode = b;
+                    while map.contains(antinode) {
+                        antinodes_with_harmonics.insert(antinode);
+                        antinode -= delta;
+                    }
                 }
             }
         }
-    }
 
-    println!("part 1: {}", antinodes.len());
-    println!("part 2: {}", antinodes_with_harmonics.len());
+        Some(antinodes_with_harmonics.len())
+    }
 }
@@ -0,0 +1,101 @@
+use aoc::{Answer, ParseError, Solution};
+
+/// A contiguous run of blocks on the disk.
+#[derive(Copy, Clone, Debug)]
+struct Span {
+    start: usize,
+    len: usize,
+}
+
+/// The ID of the file stored in each block, `None` for free blocks.
+fn blocks(files: &[Span], size: usize) -> Vec<Option<usize>> {
+    let mut blocks = vec![None; size];
+    for (id, file) in files.iter().enumerate() {
+        blocks[file.start..file.start + file.len].fill(Some(id));
+    }
+    blocks
+}
+
+fn checksum(blocks: &[Option<usize>]) -> usize {
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(position, id)| id.map(|id| position * id))
+        .sum()
+}
+
+pub struct Day09 {
+    /// The span of each file, indexed by file ID.
+    files: Vec<Span>,
+    /// The free spans between files, in disk order.
+    free: Vec<Span>,
+    size: usize,
+}
+
+impl Solution for Day09 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let map = input.trim_end();
+        let mut files = Vec::new();
+        let mut free = Vec::new();
+        let mut start = 0;
+        for (i, c) in map.char_indices() {
+            let len = c
+                .to_digit(10)
+                .ok_or_else(|| ParseError::new(input, &map[i..i + c.len_utf8()], "a digit"))?
+                as usize;
+            let span = Span { start, len };
+            if files.len() == free.len() {
+                files.push(span);
+            } else {
+                free.push(span);
+            }
+            start += len;
+        }
+        Ok(Self {
+            files,
+            free,
+            size: start,
+        })
+    }
+
+    fn part1(&self) -> impl Answer {
+        // move single blocks from the end into the leftmost free block
+        let mut blocks = blocks(&self.files, self.size);
+        let mut left = 0;
+        let mut right = blocks.len();
+        loop {
+            while left < right && blocks[left].is_some() {
+                left += 1;
+            }
+            while left < right && blocks[right - 1].is_none() {
+                right -= 1;
+            }
+            if left >= right {
+                break;
+            }
+            blocks.swap(left, right - 1);
+        }
+        Some(checksum(&blocks))
+    }
+
+    fn part2(&self) -> impl Answer {
+        // move whole files, highest ID first, into the leftmost free span that
+        // fits them
+        let mut files = self.files.clone();
+        let mut free = self.free.clone();
+        for file in files.iter_mut().rev() {
+            let Some(span) = free
+                .iter_mut()
+                .take_while(|span| span.start < file.start)
+                .find(|span| span.len >= file.len)
+            else {
+                continue;
+            };
+            file.start = span.start;
+            span.start += file.len;
+            span.len -= file.len;
+        }
+
+        Some(checksum(&blocks(&files, self.size)))
+    }
+}
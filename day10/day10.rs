@@ -1,33 +1,15 @@
+use aoc::{Answer, Grid, ParseError, Point, Solution};
 use std::collections::HashSet;
-use std::io;
 
-#[derive(Eq, Hash, PartialEq, Copy, Clone)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-fn within_bounds(p: &Point, map: &[Vec<u8>]) -> bool {
-    p.y >= 0 && (p.y as usize) < map.len() && p.x >= 0 && (p.x as usize) < map[p.y as usize].len()
-}
-
-fn hiking_trails(pos: &Point, map: &[Vec<u8>], trailtail: &mut HashSet<Point>) -> usize {
-    if map[pos.y as usize][pos.x as usize] == 9 {
+fn hiking_trails(pos: &Point, map: &Grid<u8>, trailtail: &mut HashSet<Point>) -> usize {
+    if map[*pos] == 9 {
         trailtail.insert(*pos);
         return 1;
     }
 
     let mut trails = 0;
-    let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    for (dx, dy) in directions {
-        let next_pos = Point {
-            x: pos.x + dx,
-            y: pos.y + dy,
-        };
-        if within_bounds(&next_pos, map)
-            && map[next_pos.y as usize][next_pos.x as usize]
-                == map[pos.y as usize][pos.x as usize] + 1
-        {
+    for next_pos in map.neighbors4(*pos) {
+        if map[next_pos] == map[*pos] + 1 {
             trails += hiking_trails(&next_pos, map, trailtail);
         }
     }
@@ -35,33 +17,40 @@ fn hiking_trails(pos: &Point, map: &[Vec<u8>], trailtail: &mut HashSet<Point>) -
     trails
 }
 
-fn main() {
-    let map: Vec<Vec<_>> = io::stdin()
-        .lines()
-        .map(|line| {
-            line.expect("error reading input")
-                .bytes()
-                .map(|c| c - b'0')
-                .collect()
+pub struct Day10 {
+    map: Grid<u8>,
+}
+
+impl Day10 {
+    fn trailheads(&self) -> impl Iterator<Item = Point> + '_ {
+        self.map.points().filter(|&p| self.map[p] == 0)
+    }
+}
+
+impl Solution for Day10 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            map: Grid::parse(input, |c| c.to_digit(10).map(|h| h as u8).ok_or("a height"))?,
         })
-        .collect();
+    }
 
-    let mut score_sum = 0;
-    let mut rating_sum = 0;
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if map[y][x] == 0 {
-                let trailhead = Point {
-                    x: x as isize,
-                    y: y as isize,
-                };
-                let mut trailtails = HashSet::new();
-                let x = hiking_trails(&trailhead, &map, &mut trailtails);
-                score_sum += trailtails.len();
-                rating_sum += x;
-            }
-        }
+    fn part1(&self) -> impl Answer {
+        Some(
+            self.trailheads()
+                .map(|trailhead| {
+                    let mut trailtails = HashSet::new();
+                    hiking_trails(&trailhead, &self.map, &mut trailtails);
+                    trailtails.len()
+                })
+                .sum::<usize>(),
+        )
+    }
+
+    fn part2(&self) -> impl Answer {
+        Some(
+            self.trailheads()
+                .map(|trailhead| hiking_trails(&trailhead, &self.map, &mut HashSet::new()))
+                .sum::<usize>(),
+        )
     }
-    println!("part 1: {}", score_sum);
-    println!("part 2: {}", rating_sum);
 }
@@ -1,5 +1,5 @@
+use aoc::{parse, Answer, Param, Params, ParseError, Solution};
 use std::collections::HashMap;
-use std::io;
 
 type Stone = u64;
 
@@ -31,32 +31,66 @@ fn count_stones(stone: Stone, n: usize, mem: &mut HashMap<(Stone, usize), usize>
             return count;
         }
 
-        let count = blink(stone).iter().map(|&stone| count_stones(stone, n - 1, mem)).sum();
+        let count = blink(stone)
+            .iter()
+            .map(|&stone| count_stones(stone, n - 1, mem))
+            .sum();
         mem.insert((stone, n), count);
         count
     }
 }
 
-fn main() {
-    let input = io::read_to_string(io::stdin()).expect("error reading input");
-    let stones: Vec<_> = input
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().expect("error parsing stone number"))
-        .collect();
-
-    let mut mem = HashMap::new();
-    println!(
-        "part 1: {}",
-        stones
-            .iter()
-            .map(|&stone| count_stones(stone, 25, &mut mem))
-            .sum::<usize>()
-    );
-    println!(
-        "part 2: {}",
-        stones
+pub struct Day11 {
+    stones: Vec<Stone>,
+    part1_blinks: usize,
+    part2_blinks: usize,
+}
+
+impl Day11 {
+    fn count_stones(&self, blinks: usize) -> usize {
+        let mut mem = HashMap::new();
+        self.stones
             .iter()
-            .map(|&stone| count_stones(stone, 75, &mut mem))
+            .map(|&stone| count_stones(stone, blinks, &mut mem))
             .sum::<usize>()
-    );
+    }
+}
+
+impl Solution for Day11 {
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "part1-blinks",
+            default: 25,
+            description: "times the stones change in part 1",
+        },
+        Param {
+            name: "part2-blinks",
+            default: 75,
+            description: "times the stones change in part 2",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &Params::new(Self::PARAMS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let stones = input
+            .split_whitespace()
+            .map(|s| parse(input, s, "a stone number"))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            stones,
+            part1_blinks: params.get("part1-blinks") as usize,
+            part2_blinks: params.get("part2-blinks") as usize,
+        })
+    }
+
+    fn part1(&self) -> impl Answer {
+        Some(self.count_stones(self.part1_blinks))
+    }
+
+    fn part2(&self) -> impl Answer {
+        Some(self.count_stones(self.part2_blinks))
+    }
 }
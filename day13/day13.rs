@@ -1,4 +1,4 @@
-use std::io;
+use aoc::{parse, Answer, Param, Params, ParseError, Solution};
 use std::str::FromStr;
 
 #[derive(Copy, Clone, Debug)]
@@ -14,55 +14,69 @@ struct ClawMachine {
     prize: Point,
 }
 
-#[derive(Debug)]
-struct ParseClawMachineError;
-
-fn parse_button(s: &str) -> Result<Point, ParseClawMachineError> {
-    let (_, ops) = s.split_once(": ").ok_or(ParseClawMachineError)?;
-    let (x_op, y_op) = ops.split_once(", ").ok_or(ParseClawMachineError)?;
+fn parse_button(input: &str, s: &str) -> Result<Point, ParseError> {
+    let expected = "a button 'Button A: X+dx, Y+dy'";
+    let (_, ops) = s
+        .split_once(": ")
+        .ok_or_else(|| ParseError::new(input, s, expected))?;
+    let (x_op, y_op) = ops
+        .split_once(", ")
+        .ok_or_else(|| ParseError::new(input, s, expected))?;
     if x_op.len() < 2 || y_op.len() < 2 {
-        Err(ParseClawMachineError)
+        Err(ParseError::new(input, ops, expected))
     } else {
         Ok(Point {
-            x: x_op[2..]
-                .parse::<usize>()
-                .map_err(|_| ParseClawMachineError)?,
-            y: y_op[2..]
-                .parse::<usize>()
-                .map_err(|_| ParseClawMachineError)?,
+            x: parse(input, &x_op[2..], "an integer")?,
+            y: parse(input, &y_op[2..], "an integer")?,
         })
     }
 }
 
-fn parse_prize(s: &str) -> Result<Point, ParseClawMachineError> {
-    let (_, vals) = s.split_once(": ").ok_or(ParseClawMachineError)?;
-    let (x, y) = vals.split_once(", ").ok_or(ParseClawMachineError)?;
-    let (_, x) = x.split_once('=').ok_or(ParseClawMachineError)?;
-    let (_, y) = y.split_once('=').ok_or(ParseClawMachineError)?;
+fn parse_prize(input: &str, s: &str) -> Result<Point, ParseError> {
+    let expected = "a prize 'Prize: X=x, Y=y'";
+    let (_, vals) = s
+        .split_once(": ")
+        .ok_or_else(|| ParseError::new(input, s, expected))?;
+    let (x, y) = vals
+        .split_once(", ")
+        .ok_or_else(|| ParseError::new(input, s, expected))?;
+    let (_, x) = x
+        .split_once('=')
+        .ok_or_else(|| ParseError::new(input, x, "'X=x'"))?;
+    let (_, y) = y
+        .split_once('=')
+        .ok_or_else(|| ParseError::new(input, y, "'Y=y'"))?;
     Ok(Point {
-        x: x.parse::<usize>().map_err(|_| ParseClawMachineError)?,
-        y: y.parse::<usize>().map_err(|_| ParseClawMachineError)?,
+        x: parse(input, x, "an integer")?,
+        y: parse(input, y, "an integer")?,
     })
 }
 
 impl FromStr for ClawMachine {
-    type Err = ParseClawMachineError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines: Vec<_> = s.lines().collect();
         if lines.len() != 3 {
-            Err(ParseClawMachineError)
+            Err(ParseError::new(s, s, "two buttons and a prize"))
         } else {
             Ok(ClawMachine {
-                button_a_delta: parse_button(lines[0])?,
-                button_b_delta: parse_button(lines[1])?,
-                prize: parse_prize(lines[2])?,
+                button_a_delta: parse_button(s, lines[0])?,
+                button_b_delta: parse_button(s, lines[1])?,
+                prize: parse_prize(s, lines[2])?,
             })
         }
     }
 }
 
-fn solve(machine: &ClawMachine, max_presses: usize) -> Option<usize> {
+/// Tokens spent pressing each button.
+#[derive(Copy, Clone, Debug)]
+struct Costs {
+    a: usize,
+    b: usize,
+}
+
+fn solve(machine: &ClawMachine, max_presses: usize, costs: Costs) -> Option<usize> {
     let (x, y) = (machine.prize.x as f64, machine.prize.y as f64);
     let (dax, day) = (
         machine.button_a_delta.x as f64,
@@ -82,48 +96,86 @@ fn solve(machine: &ClawMachine, max_presses: usize) -> Option<usize> {
         && a * machine.button_a_delta.x + b * machine.button_b_delta.x == machine.prize.x
         && a * machine.button_a_delta.y + b * machine.button_b_delta.y == machine.prize.y
     {
-        Some(a * 3 + b)
+        Some(a * costs.a + b * costs.b)
     } else {
         None
     }
 }
 
-fn main() {
-    let input = io::read_to_string(io::stdin()).expect("error reading input");
-    let machines: Vec<_> = input
-        .split("\n\n")
-        .map(|s| {
-            s.parse::<ClawMachine>()
-                .expect("error parsing claw machine info")
+pub struct Day13 {
+    machines: Vec<ClawMachine>,
+    costs: Costs,
+    /// Added to both prize coordinates in part 2.
+    prize_offset: usize,
+}
+
+impl Solution for Day13 {
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "a-cost",
+            default: 3,
+            description: "tokens to press button A",
+        },
+        Param {
+            name: "b-cost",
+            default: 1,
+            description: "tokens to press button B",
+        },
+        Param {
+            name: "prize-offset",
+            default: 10000000000000,
+            description: "added to the prize coordinates in part 2",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &Params::new(Self::PARAMS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let machines = input
+            .split("\n\n")
+            .map(|s| s.parse::<ClawMachine>().map_err(|e| e.within(input, s)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            machines,
+            costs: Costs {
+                a: params.get("a-cost") as usize,
+                b: params.get("b-cost") as usize,
+            },
+            prize_offset: params.get("prize-offset") as usize,
         })
-        .collect();
+    }
 
-    println!(
-        "part 1: {}",
-        machines
-            .iter()
-            .filter_map(|machine| solve(machine, 100))
-            .sum::<usize>()
-    );
-    println!(
-        "part 2: {}",
-        machines
-            .iter()
-            .map(
-                |&ClawMachine {
-                     prize,
-                     button_a_delta,
-                     button_b_delta,
-                 }| ClawMachine {
-                    prize: Point {
-                        x: prize.x + 10000000000000,
-                        y: prize.y + 10000000000000
+    fn part1(&self) -> impl Answer {
+        Some(
+            self.machines
+                .iter()
+                .filter_map(|machine| solve(machine, 100, self.costs))
+                .sum::<usize>(),
+        )
+    }
+
+    fn part2(&self) -> impl Answer {
+        Some(
+            self.machines
+                .iter()
+                .map(
+                    |&ClawMachine {
+                         prize,
+                         button_a_delta,
+                         button_b_delta,
+                     }| ClawMachine {
+                        prize: Point {
+                            x: prize.x + self.prize_offset,
+                            y: prize.y + self.prize_offset,
+                        },
+                        button_a_delta,
+                        button_b_delta,
                     },
-                    button_a_delta,
-                    button_b_delta
-                }
-            )
-            .filter_map(|machine| solve(&machine, usize::MAX))
-            .sum::<usize>()
-    );
+                )
+                .filter_map(|machine| solve(&machine, usize::MAX, self.costs))
+                .sum::<usize>(),
+        )
+    }
 }
@@ -1,47 +1,30 @@
+use aoc::{Answer, Grid, Param, Params, ParseError, Point, Solution};
+use std::cell::OnceCell;
 use std::collections::HashSet;
-use std::io;
 use std::str::FromStr;
 
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-#[derive(Debug)]
-struct ParsePointError;
-
-impl FromStr for Point {
-    type Err = ParsePointError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').ok_or(ParsePointError)?;
-        Ok(Point {
-            x: x.parse::<isize>().map_err(|_| ParsePointError)?,
-            y: y.parse::<isize>().map_err(|_| ParsePointError)?,
-        })
-    }
-}
-
 #[derive(Copy, Clone, Debug)]
 struct Robot {
     p: Point,
     v: Point,
 }
 
-#[derive(Debug)]
-struct ParseRobotError;
-
 impl FromStr for Robot {
-    type Err = ParseRobotError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (p, v) = s.split_once(' ').ok_or(ParseRobotError)?;
-        let (_, p) = p.split_once('=').ok_or(ParseRobotError)?;
-        let (_, v) = v.split_once('=').ok_or(ParseRobotError)?;
+        let (p, v) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(s, s, "a robot 'p=x,y v=dx,dy'"))?;
+        let (_, p) = p
+            .split_once('=')
+            .ok_or_else(|| ParseError::new(s, p, "'p=x,y'"))?;
+        let (_, v) = v
+            .split_once('=')
+            .ok_or_else(|| ParseError::new(s, v, "'v=dx,dy'"))?;
         Ok(Robot {
-            p: p.parse::<Point>().map_err(|_| ParseRobotError)?,
-            v: v.parse::<Point>().map_err(|_| ParseRobotError)?,
+            p: p.parse::<Point>().map_err(|e| e.within(s, p))?,
+            v: v.parse::<Point>().map_err(|e| e.within(s, v))?,
         })
     }
 }
@@ -59,20 +42,20 @@ fn advance(robot: Robot, map_width: usize, map_height: usize) -> Robot {
 }
 
 fn longest_line(robots: &[Robot], map_width: usize, map_height: usize) -> usize {
-    let mut map = vec![vec![false; map_width]; map_height];
+    let mut map = Grid::new(map_width, map_height, false);
     for robot in robots {
-        map[robot.p.y as usize][robot.p.x as usize] = true;
+        map[robot.p] = true;
     }
     let mut longest = 0;
-    for y in 0..map_height {
+    for y in 0..map_height as isize {
         let mut x = 0;
-        while x < map_width {
-            if map[y][x] {
+        while x < map_width as isize {
+            if map[Point::new(x, y)] {
                 let mut i = 1;
-                while x + i < map_width && map[y][x + i] {
+                while map.get(Point::new(x + i, y)) == Some(&true) {
                     i += 1
                 }
-                longest = longest.max(i);
+                longest = longest.max(i as usize);
                 x += i;
             } else {
                 x += 1;
@@ -82,19 +65,21 @@ fn longest_line(robots: &[Robot], map_width: usize, map_height: usize) -> usize
     longest
 }
 
-fn display(robots: &[Robot], map_width: usize, map_height: usize) {
+/// Draws the robots as `#` on a map of `.`.
+fn picture(robots: &[Robot], map_width: usize, map_height: usize) -> String {
     let positions: HashSet<Point> = robots.iter().map(|robot| robot.p).collect();
+    let mut picture = String::new();
     for y in 0..map_height {
         for x in 0..map_width {
-            if positions.contains(&Point { x: x as isize, y: y as isize }) {
-                print!("{}", '#');
+            if positions.contains(&Point::new(x as isize, y as isize)) {
+                picture.push('#');
             } else {
-                print!("{}", '.');
+                picture.push('.');
             }
         }
-        println!();
+        picture.push('\n');
     }
-    println!();
+    picture
 }
 
 fn quadrant_count(
@@ -131,36 +116,93 @@ fn quadrant_count(
         .unwrap()
 }
 
-fn main() {
-    let mut robots: Vec<_> = io::stdin()
-        .lines()
-        .map(|line| {
-            line.expect("error reading input")
-                .parse::<Robot>()
-                .expect("error parsing robot")
-        })
-        .collect();
+pub struct Day14 {
+    robots: Vec<Robot>,
+    map_width: usize,
+    map_height: usize,
+    seconds: usize,
+    /// The robots where part 2 found the tree.
+    tree: OnceCell<Vec<Robot>>,
+}
 
-    let map_width = 101;
-    let map_height = 103;
+impl Solution for Day14 {
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "width",
+            default: 101,
+            description: "width of the map",
+        },
+        Param {
+            name: "height",
+            default: 103,
+            description: "height of the map",
+        },
+        Param {
+            name: "seconds",
+            default: 100,
+            description: "seconds the robots move for in part 1",
+        },
+    ];
 
-    for _ in 1..=100 {
-        for robot in &mut robots {
-            *robot = advance(*robot, map_width, map_height);
-        }
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &Params::new(Self::PARAMS))
     }
 
-    let (tl, bl, tr, br) = quadrant_count(&robots, map_width, map_height);
-    println!("part 1: {}", tl * tr * bl * br);
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let map_width = params.get("width") as usize;
+        let map_height = params.get("height") as usize;
+        let robots = input
+            .lines()
+            .map(|line| {
+                let robot = line.parse::<Robot>().map_err(|e| e.within(input, line))?;
+                if robot.p.x < 0
+                    || robot.p.x >= map_width as isize
+                    || robot.p.y < 0
+                    || robot.p.y >= map_height as isize
+                {
+                    return Err(ParseError::new(input, line, "a robot within the map"));
+                }
+                Ok(robot)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            robots,
+            map_width,
+            map_height,
+            seconds: params.get("seconds") as usize,
+            tree: OnceCell::new(),
+        })
+    }
 
-    for i in 101.. {
-        for robot in &mut robots {
-            *robot = advance(*robot, map_width, map_height);
+    fn part1(&self) -> impl Answer {
+        let mut robots = self.robots.clone();
+        for _ in 1..=self.seconds {
+            for robot in &mut robots {
+                *robot = advance(*robot, self.map_width, self.map_height);
+            }
         }
-        if longest_line(&robots, map_width, map_height) > 10 {
-            display(&robots, map_width, map_height);
-            println!("part 2: {}", i);
-            break;
+
+        let (tl, bl, tr, br) = quadrant_count(&robots, self.map_width, self.map_height);
+        Some(tl * tr * bl * br)
+    }
+
+    fn part2(&self) -> impl Answer {
+        // robot positions repeat after map_width * map_height seconds
+        let mut robots = self.robots.clone();
+        for i in 1..=self.map_width * self.map_height {
+            for robot in &mut robots {
+                *robot = advance(*robot, self.map_width, self.map_height);
+            }
+            if longest_line(&robots, self.map_width, self.map_height) > 10 {
+                self.tree.get_or_init(|| robots);
+                return Some(i);
+            }
         }
+        None
+    }
+
+    fn diagnostics(&self) -> Option<String> {
+        let robots = self.tree.get()?;
+        Some(picture(robots, self.map_width, self.map_height))
     }
 }
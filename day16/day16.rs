@@ -1,209 +1,394 @@
+pub mod generate;
+pub mod render;
+pub mod routes;
+
+use aoc::{Answer, Direction, Grid, Param, Params, ParseError, Point, Solution};
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::io;
 
 #[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Copy, Clone, Debug)]
-struct Point {
-    x: isize,
-    y: isize,
+pub struct Reindeer {
+    pub position: Point,
+    pub direction: Direction,
 }
 
-impl Point {
-    fn next_position(&self, direction: Direction) -> Self {
-        let (dx, dy) = match direction {
-            Direction::North => (0, -1),
-            Direction::South => (0, 1),
-            Direction::West => (-1, 0),
-            Direction::East => (1, 0),
-        };
-        Self {
-            x: self.x + dx,
-            y: self.y + dy,
-        }
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    /// The score so far plus the heuristic's estimate of the rest.
+    estimate: usize,
+    score: usize,
+    reindeer: Reindeer,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.reindeer.cmp(&other.reindeer))
     }
 }
 
-fn find_tile(tile: char, map: &[Vec<char>]) -> Option<Point> {
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if map[y][x] == tile {
-                return Some(Point {
-                    x: x as isize,
-                    y: y as isize,
-                });
-            }
-        }
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
-    None
 }
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Copy, Clone, Debug)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
+/// What each move of the reindeer costs. Moves without a cost can't be made.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Costs {
+    /// A step to the next tile in the direction faced.
+    pub forward: usize,
+    /// A quarter turn on the spot.
+    pub turn: usize,
+    /// A half turn on the spot, instead of two quarter turns.
+    pub u_turn: Option<usize>,
+    /// A step back to the previous tile, still facing the same way.
+    pub backward: Option<usize>,
 }
 
-impl Direction {
-    fn turn_left(&self) -> Self {
-        match self {
-            Self::North => Self::West,
-            Self::South => Self::East,
-            Self::West => Self::South,
-            Self::East => Self::North,
-        }
+impl Costs {
+    /// The puzzle's reindeer, which only steps forward and turns a quarter
+    /// at a time.
+    pub const REINDEER: Self = Self {
+        forward: 1,
+        turn: 1000,
+        u_turn: None,
+        backward: None,
+    };
+
+    /// The moves allowed from `reindeer`, with their costs.
+    fn moves(&self, reindeer: Reindeer) -> impl Iterator<Item = (usize, Reindeer)> {
+        let Reindeer {
+            position,
+            direction,
+        } = reindeer;
+        [
+            Some((self.turn, position, direction.turn_right())),
+            Some((self.turn, position, direction.turn_left())),
+            Some((self.forward, position.next_position(direction), direction)),
+            self.u_turn
+                .map(|cost| (cost, position, direction.reverse())),
+            self.backward
+                .map(|cost| (cost, position.next_position(direction.reverse()), direction)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(cost, position, direction)| {
+            (
+                cost,
+                Reindeer {
+                    position,
+                    direction,
+                },
+            )
+        })
     }
 
-    fn turn_right(&self) -> Self {
-        match self {
-            Self::North => Self::East,
-            Self::South => Self::West,
-            Self::West => Self::North,
-            Self::East => Self::South,
-        }
+    /// A lower bound on the cost of getting from `reindeer` to `end`: the
+    /// steps it takes without walls, and the turns needed to face the ways
+    /// those steps go. Each move lowers it by at most its own cost, so A*
+    /// never has to expand a state twice.
+    fn heuristic(&self, reindeer: &Reindeer, end: Point) -> usize {
+        let step = self.backward.map_or(self.forward, |b| b.min(self.forward));
+        let offset = end - reindeer.position;
+        // the ways the reindeer has to go along each axis
+        let way = |offset: isize, back, forth| match offset.cmp(&0) {
+            Ordering::Less => Some(back),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(forth),
+        };
+        let needed = (
+            way(offset.x, Direction::Left, Direction::Right),
+            way(offset.y, Direction::Up, Direction::Down),
+        );
+        let facing = reindeer.direction;
+        // facing a way that must be gone, or away from it when the reindeer
+        // can step backwards
+        let usable = |direction: Direction| {
+            direction == facing || (self.backward.is_some() && direction == facing.reverse())
+        };
+        // turning around is two quarter turns or a U-turn, whichever is
+        // cheaper
+        let half_turn = self.u_turn.map_or(2 * self.turn, |u| u.min(2 * self.turn));
+        let turns = match needed {
+            (None, None) => 0,
+            (Some(direction), None) | (None, Some(direction)) => {
+                if usable(direction) {
+                    0
+                } else if direction == facing.reverse() {
+                    half_turn
+                } else {
+                    self.turn
+                }
+            }
+            // with both axes to walk along there is a turn between them, and
+            // facing away from the goal takes turning around as well
+            (Some(x), Some(y)) if usable(x) || usable(y) => self.turn,
+            (Some(_), Some(_)) => self.turn + self.u_turn.map_or(self.turn, |u| u.min(self.turn)),
+        };
+        step * offset.x.unsigned_abs() + step * offset.y.unsigned_abs() + turns
+    }
+
+    /// The cost of moving from `from` to `to`, if that's a single move.
+    fn cost(&self, from: Reindeer, to: Reindeer) -> Option<usize> {
+        self.moves(from)
+            .filter(|&(_, next)| next == to)
+            .map(|(cost, _)| cost)
+            .min()
     }
 }
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Copy, Clone, Debug)]
-struct Reindeer {
-    position: Point,
-    direction: Direction,
+fn map_free_tile(position: Point, map: &Grid<char>) -> bool {
+    !matches!(map.get(position), None | Some('#'))
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct State {
-    score: usize,
-    reindeer: Reindeer,
+/// How `best_path` picks the next state to expand.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Search {
+    /// The state with the lowest score.
+    Dijkstra,
+    /// The state with the lowest score plus `Costs::heuristic`. That skips
+    /// most of an open maze, but the heuristic can't see the turns walls
+    /// force, so in a maze of corridors it expands about as much.
+    AStar,
 }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .score
-            .cmp(&self.score)
-            .then_with(|| self.reindeer.cmp(&other.reindeer))
+/// One of the best paths from `start` to `end`.
+///
+/// `prev` holds, for each state, the states whose score plus the cost of
+/// the move equals its own. Free moves make it cyclic, so the path only
+/// steps back to states it hasn't visited, and backs out of dead ends.
+fn one_path(
+    start: Reindeer,
+    end: Reindeer,
+    prev: &HashMap<Reindeer, Vec<Reindeer>>,
+) -> Vec<Reindeer> {
+    let mut path = vec![end];
+    let mut visited = HashSet::from([end]);
+    while let Some(&reindeer) = path.last() {
+        if reindeer == start {
+            break;
+        }
+        match prev[&reindeer].iter().find(|&&p| !visited.contains(&p)) {
+            Some(&p) => {
+                visited.insert(p);
+                path.push(p);
+            }
+            None => {
+                path.pop();
+            }
+        }
     }
+    path.reverse();
+    path
 }
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+/// The tiles of every best path to the `ends`. Each state is visited once,
+/// however many best paths go through it.
+fn tiles(ends: &[Reindeer], prev: &HashMap<Reindeer, Vec<Reindeer>>) -> HashSet<Point> {
+    let mut visited: HashSet<_> = ends.iter().copied().collect();
+    let mut stack = ends.to_vec();
+    while let Some(reindeer) = stack.pop() {
+        for prev_reindeer in prev.get(&reindeer).into_iter().flatten() {
+            if visited.insert(*prev_reindeer) {
+                stack.push(*prev_reindeer);
+            }
+        }
     }
+    visited.iter().map(|reindeer| reindeer.position).collect()
 }
 
-fn map_free_tile(position: Point, map: &[Vec<char>]) -> bool {
-    if position.y < 0 || position.x < 0 {
-        return false;
-    }
-    match map
-        .get(position.y as usize)
-        .and_then(|row| row.get(position.x as usize))
-    {
-        None | Some('#') => false,
-        _ => true,
-    }
+fn count_tiles(ends: &[Reindeer], prev: &HashMap<Reindeer, Vec<Reindeer>>) -> usize {
+    tiles(ends, prev).len()
+}
+
+/// Every tile on a best path through the maze, and one of those paths.
+#[derive(Clone, Debug)]
+pub struct BestPaths {
+    pub score: usize,
+    pub tiles: HashSet<Point>,
+    /// Each state of the reindeer from the start to the end.
+    pub path: Vec<Reindeer>,
 }
 
-fn best_path(
+#[derive(Clone)]
+pub struct Day16 {
+    map: Grid<char>,
     start: Point,
-    start_direction: Direction,
     end: Point,
-    map: &[Vec<char>],
-    prev: &mut HashMap<Reindeer, Vec<Reindeer>>,
-) -> Option<(Reindeer, usize)> {
-    let mut scores = HashMap::new();
-    let mut pq = BinaryHeap::new();
-
-    let reindeer = Reindeer {
-        position: start,
-        direction: start_direction,
-    };
-    scores.insert(reindeer, 0);
-    prev.insert(reindeer, Vec::new());
-    pq.push(State { score: 0, reindeer });
+    costs: Costs,
+    search: Search,
+}
 
-    while let Some(State { score, reindeer }) = pq.pop() {
-        if reindeer.position == end {
-            return Some((reindeer, score));
-        }
+impl Day16 {
+    /// The same maze, walked with other costs.
+    pub fn with_costs(self, costs: Costs) -> Self {
+        Self { costs, ..self }
+    }
+
+    /// The same maze, searched another way.
+    pub fn with_search(self, search: Search) -> Self {
+        Self { search, ..self }
+    }
 
-        if score > *scores.get(&reindeer).unwrap_or(&usize::MAX) {
-            continue;
+    fn start_reindeer(&self) -> Reindeer {
+        Reindeer {
+            position: self.start,
+            direction: Direction::Right,
         }
+    }
 
-        let position = reindeer.position;
-        let direction = reindeer.direction;
-        let next = [
-            (score + 1000, position, direction.turn_right()),
-            (score + 1000, position, direction.turn_left()),
-            (score + 1, position.next_position(direction), direction),
-        ];
-
-        for (score, position, direction) in next {
-            let next_state = State {
-                score,
-                reindeer: Reindeer {
-                    position,
-                    direction,
-                },
-            };
-            if !map_free_tile(position, map) {
+    pub fn best_paths(&self) -> Option<BestPaths> {
+        let mut prev = HashMap::new();
+        let (ends, score) = self.best_path(&mut prev)?;
+        Some(BestPaths {
+            score,
+            tiles: tiles(&ends, &prev),
+            path: one_path(self.start_reindeer(), ends[0], &prev),
+        })
+    }
+
+    fn best_path(
+        &self,
+        prev: &mut HashMap<Reindeer, Vec<Reindeer>>,
+    ) -> Option<(Vec<Reindeer>, usize)> {
+        let heuristic = |reindeer: &Reindeer| match self.search {
+            Search::Dijkstra => Some(0),
+            Search::AStar => Some(self.costs.heuristic(reindeer, self.end)),
+        };
+        self.search(self.start_reindeer(), |_, _| true, heuristic, prev)
+    }
+
+    /// A* search for the best paths from `start` to any state at `E`, making
+    /// only the moves `allowed` lets through, with every equal-cost
+    /// predecessor of the states on the way in `prev`.
+    ///
+    /// `heuristic` must never overestimate the cost left to reach `E`, nor
+    /// drop by more than the cost of a move, and is `None` where `E` can't
+    /// be reached at all; `|_| Some(0)` makes this Dijkstra's search. Returns
+    /// every end state reached with the best score, in the order they were
+    /// popped, which is the same whatever the heuristic.
+    fn search(
+        &self,
+        start: Reindeer,
+        allowed: impl Fn(&Reindeer, &Reindeer) -> bool,
+        heuristic: impl Fn(&Reindeer) -> Option<usize>,
+        prev: &mut HashMap<Reindeer, Vec<Reindeer>>,
+    ) -> Option<(Vec<Reindeer>, usize)> {
+        let mut scores = HashMap::new();
+        let mut pq = BinaryHeap::new();
+        let mut best = None;
+
+        let reindeer = start;
+        scores.insert(reindeer, 0);
+        prev.insert(reindeer, Vec::new());
+        pq.push(State {
+            estimate: heuristic(&reindeer)?,
+            score: 0,
+            reindeer,
+        });
+
+        while let Some(State {
+            estimate,
+            score,
+            reindeer,
+        }) = pq.pop()
+        {
+            // Keep going while states could still be on an equally good
+            // path, so all of their predecessors are collected.
+            if best.as_ref().is_some_and(|&(_, best)| estimate > best) {
+                break;
+            }
+
+            if score > *scores.get(&reindeer).unwrap_or(&usize::MAX) {
                 continue;
             }
 
-            match next_state
-                .score
-                .cmp(scores.get(&next_state.reindeer).unwrap_or(&usize::MAX))
-            {
-                Ordering::Less => {
-                    pq.push(next_state);
-                    scores.insert(next_state.reindeer, next_state.score);
-                    prev.insert(next_state.reindeer, vec![reindeer]);
+            if reindeer.position == self.end {
+                // ends are popped in order of score, as their estimate is
+                // exact
+                match &mut best {
+                    None => best = Some((vec![reindeer], score)),
+                    Some((ends, best)) if score == *best => ends.push(reindeer),
+                    Some(_) => {}
                 }
-                Ordering::Equal => prev.get_mut(&next_state.reindeer).unwrap().push(reindeer),
-                _ => {}
+                continue;
             }
-        }
-    }
 
-    None
-}
+            for (cost, next) in self.costs.moves(reindeer) {
+                if !map_free_tile(next.position, &self.map) || !allowed(&reindeer, &next) {
+                    continue;
+                }
+                let Some(estimate) = heuristic(&next) else {
+                    continue;
+                };
 
-fn count_tiles_rec(
-    reindeer: &Reindeer,
-    prev: &HashMap<Reindeer, Vec<Reindeer>>,
-    count: &mut HashSet<Point>,
-) {
-    count.insert(reindeer.position);
-    if let Some(prev_reindeers) = prev.get(reindeer) {
-        for prev_reindeer in prev_reindeers {
-            count_tiles_rec(prev_reindeer, prev, count);
+                let next_score = score + cost;
+                match next_score.cmp(scores.get(&next).unwrap_or(&usize::MAX)) {
+                    Ordering::Less => {
+                        pq.push(State {
+                            estimate: next_score + estimate,
+                            score: next_score,
+                            reindeer: next,
+                        });
+                        scores.insert(next, next_score);
+                        prev.insert(next, vec![reindeer]);
+                    }
+                    Ordering::Equal => prev.get_mut(&next).unwrap().push(reindeer),
+                    _ => {}
+                }
+            }
         }
+
+        best
     }
 }
 
-fn count_tiles(reindeer: &Reindeer, prev: &HashMap<Reindeer, Vec<Reindeer>>) -> usize {
-    let mut count = HashSet::new();
-    count_tiles_rec(reindeer, prev, &mut count);
-    count.len()
-}
+impl Solution for Day16 {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "a-star",
+        default: 0,
+        description: "1 to search with A* rather than Dijkstra's algorithm",
+    }];
 
-fn main() {
-    let map: Vec<Vec<char>> = io::stdin()
-        .lines()
-        .map(|line| line.expect("error reading input").chars().collect())
-        .collect();
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &Params::new(Self::PARAMS))
+    }
 
-    let start = find_tile('S', &map).expect("no start tile");
-    let end = find_tile('E', &map).expect("no end tile");
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let map = Grid::parse(input, |c| match c {
+            '.' | '#' | 'S' | 'E' => Ok(c),
+            _ => Err("'.', '#', 'S' or 'E'"),
+        })?;
+        let start = map
+            .find(&'S')
+            .ok_or_else(|| ParseError::end_of_input(input, "a start tile 'S'"))?;
+        let end = map
+            .find(&'E')
+            .ok_or_else(|| ParseError::end_of_input(input, "an end tile 'E'"))?;
+        Ok(Self {
+            map,
+            start,
+            end,
+            costs: Costs::REINDEER,
+            search: match params.get("a-star") {
+                0 => Search::Dijkstra,
+                _ => Search::AStar,
+            },
+        })
+    }
 
-    let mut prev = HashMap::new();
-    let (reindeer, score) =
-        best_path(start, Direction::East, end, &map, &mut prev).expect("no path found");
+    fn part1(&self) -> impl Answer {
+        self.best_path(&mut HashMap::new()).map(|(_, score)| score)
+    }
 
-    println!("part 1: {}", score);
-    println!("part 2: {}", count_tiles(&reindeer, &prev));
+    fn part2(&self) -> impl Answer {
+        let mut prev = HashMap::new();
+        self.best_path(&mut prev)
+            .map(|(ends, _)| count_tiles(&ends, &prev))
+    }
 }
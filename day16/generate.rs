@@ -0,0 +1,57 @@
+//! Random mazes much larger than the puzzle's, for comparing the searches.
+
+use aoc::Rng;
+
+/// A maze of `width` by `height` corridor cells, with `S` in the bottom left
+/// corner and `E` in the top right one like the puzzle's. The corridors form
+/// a tree, and then `loops` in 100 of the remaining walls between two cells
+/// are knocked through, giving the reindeer a choice of ways. A maze
+/// smaller than 2 by 1 cells is widened to that, keeping `S` and `E` apart.
+pub fn maze(rng: &mut Rng, width: usize, height: usize, loops: u64) -> String {
+    let height = height.max(1);
+    let width = width.max(if height == 1 { 2 } else { 1 });
+    let mut tiles = vec![vec!['#'; 2 * width + 1]; 2 * height + 1];
+    let mut visited = vec![vec![false; width]; height];
+
+    // depth-first, carving a corridor to a random unvisited neighbour until
+    // there is none and backtracking
+    let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+    visited[0][0] = true;
+    tiles[1][1] = '.';
+    while let Some(&(x, y)) = stack.last() {
+        let neighbours: Vec<(usize, usize)> = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ]
+        .into_iter()
+        .filter(|&(x, y)| x < width && y < height && !visited[y][x])
+        .collect();
+        if neighbours.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = *rng.pick(&neighbours);
+        visited[ny][nx] = true;
+        tiles[2 * ny + 1][2 * nx + 1] = '.';
+        tiles[y + ny + 1][x + nx + 1] = '.';
+        stack.push((nx, ny));
+    }
+
+    for (y, row) in tiles.iter_mut().enumerate().take(2 * height).skip(1) {
+        for (x, tile) in row.iter_mut().enumerate().take(2 * width).skip(1) {
+            // walls between two cells have one odd and one even coordinate
+            if (x + y) % 2 == 1 && *tile == '#' && rng.chance(loops, 100) {
+                *tile = '.';
+            }
+        }
+    }
+
+    tiles[2 * height - 1][1] = 'S';
+    tiles[1][2 * width - 1] = 'E';
+    tiles
+        .iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
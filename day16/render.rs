@@ -0,0 +1,69 @@
+//! Pictures of the maze with its best paths drawn in.
+
+use crate::{BestPaths, Day16};
+use aoc::{Direction, Grid};
+
+/// The colour of each kind of tile in `ppm` pictures.
+fn colour(tile: char) -> [u8; 3] {
+    match tile {
+        '#' => [40, 40, 40],
+        'S' => [40, 170, 60],
+        'E' => [40, 90, 200],
+        'O' => [240, 170, 40],
+        '^' | 'v' | '<' | '>' => [210, 40, 40],
+        _ => [235, 235, 235],
+    }
+}
+
+fn arrow(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '^',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+        Direction::Right => '>',
+    }
+}
+
+impl Day16 {
+    /// The maze with every tile on a best path marked `O`, and one of those
+    /// paths drawn with arrows showing which way it leaves each tile.
+    pub fn render(&self) -> Option<Grid<char>> {
+        Some(self.annotate(&self.best_paths()?))
+    }
+
+    pub fn annotate(&self, best: &BestPaths) -> Grid<char> {
+        let mut map = self.map.clone();
+        for &tile in &best.tiles {
+            map[tile] = 'O';
+        }
+        // the direction a tile is left in is the last one the reindeer faces
+        // there
+        for reindeer in &best.path {
+            map[reindeer.position] = arrow(reindeer.direction);
+        }
+        map[self.start] = 'S';
+        map[self.end] = 'E';
+        map
+    }
+}
+
+/// `map` as a binary PPM image, with each tile `scale` pixels square.
+pub fn ppm(map: &Grid<char>, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    let mut image = format!(
+        "P6\n{} {}\n255\n",
+        map.width() * scale,
+        map.height() * scale
+    )
+    .into_bytes();
+    for row in map.rows() {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|&tile| colour(tile).repeat(scale))
+            .collect();
+        for _ in 0..scale {
+            image.extend(&line);
+        }
+    }
+    image
+}
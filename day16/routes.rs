@@ -0,0 +1,159 @@
+//! The best few routes through the maze rather than just the best ones, by
+//! Yen's algorithm on top of an A* search guided by the costs left to `E`.
+
+use crate::{map_free_tile, one_path, Day16, Reindeer, State};
+use aoc::Direction;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+
+/// A way through the maze: every state of the reindeer from the start to
+/// the end, and what it costs.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Route {
+    pub score: usize,
+    pub states: Vec<Reindeer>,
+}
+
+impl Day16 {
+    /// What `states` costs to walk through, one move at a time.
+    fn score(&self, states: &[Reindeer]) -> usize {
+        states
+            .windows(2)
+            .map(|pair| self.costs.cost(pair[0], pair[1]).unwrap())
+            .sum()
+    }
+
+    /// The states a single move reaches `reindeer` from, with its cost.
+    fn moves_to(&self, reindeer: Reindeer) -> impl Iterator<Item = (usize, Reindeer)> + '_ {
+        let Reindeer {
+            position,
+            direction,
+        } = reindeer;
+        let turns = Direction::ALL.map(|direction| Reindeer {
+            position,
+            direction,
+        });
+        // steps keep the direction, forward from behind or backward from
+        // ahead
+        let steps = [direction.reverse(), direction].map(|side| Reindeer {
+            position: position.next_position(side),
+            direction,
+        });
+        turns
+            .into_iter()
+            .chain(steps)
+            .filter(|from| map_free_tile(from.position, &self.map))
+            .filter_map(move |from| Some((self.costs.cost(from, reindeer)?, from)))
+    }
+
+    /// What the best way on to `E` costs from every state that has one,
+    /// found by a single search backwards from `E`.
+    fn costs_to_end(&self) -> HashMap<Reindeer, usize> {
+        let mut scores = HashMap::new();
+        let mut pq = BinaryHeap::new();
+        for direction in Direction::ALL {
+            let reindeer = Reindeer {
+                position: self.end,
+                direction,
+            };
+            scores.insert(reindeer, 0);
+            pq.push(State {
+                estimate: 0,
+                score: 0,
+                reindeer,
+            });
+        }
+        while let Some(State {
+            score, reindeer, ..
+        }) = pq.pop()
+        {
+            if score > scores[&reindeer] {
+                continue;
+            }
+            for (cost, from) in self.moves_to(reindeer) {
+                let score = score + cost;
+                if score < *scores.get(&from).unwrap_or(&usize::MAX) {
+                    scores.insert(from, score);
+                    pq.push(State {
+                        estimate: score,
+                        score,
+                        reindeer: from,
+                    });
+                }
+            }
+        }
+        scores
+    }
+
+    /// The best route from `spur` that avoids the states in `avoid` and
+    /// doesn't make any of the moves in `blocked`, searched with the costs
+    /// left to `E` as the A* heuristic.
+    fn spur_route(
+        &self,
+        spur: Reindeer,
+        avoid: &HashSet<Reindeer>,
+        blocked: &HashSet<(Reindeer, Reindeer)>,
+        to_end: &HashMap<Reindeer, usize>,
+    ) -> Option<Vec<Reindeer>> {
+        let mut prev = HashMap::new();
+        let (ends, _) = self.search(
+            spur,
+            |from, to| !avoid.contains(to) && !blocked.contains(&(*from, *to)),
+            |reindeer| to_end.get(reindeer).copied(),
+            &mut prev,
+        )?;
+        Some(one_path(spur, ends[0], &prev))
+    }
+
+    /// The `k` cheapest routes from `S` to `E` that don't pass through the
+    /// same state twice, cheapest first.
+    ///
+    /// Each route after the first follows one found earlier up to some
+    /// state, the spur, then takes the best way on to the end that leaves
+    /// the spur differently from every earlier route sharing that start.
+    pub fn k_best_paths(&self, k: usize) -> Vec<Route> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let to_end = self.costs_to_end();
+        let Some(first) = self.spur_route(
+            self.start_reindeer(),
+            &HashSet::new(),
+            &HashSet::new(),
+            &to_end,
+        ) else {
+            return Vec::new();
+        };
+        let mut routes = vec![Route {
+            score: self.score(&first),
+            states: first,
+        }];
+        let mut candidates = BTreeSet::new();
+        while routes.len() < k {
+            let last = &routes[routes.len() - 1].states;
+            for i in 0..last.len() - 1 {
+                let root = &last[..=i];
+                let blocked = routes
+                    .iter()
+                    .filter(|route| route.states.starts_with(root))
+                    .filter_map(|route| Some((route.states[i], *route.states.get(i + 1)?)))
+                    .collect();
+                let avoid = root[..i].iter().copied().collect();
+                let Some(spur) = self.spur_route(last[i], &avoid, &blocked, &to_end) else {
+                    continue;
+                };
+                let states: Vec<_> = root[..i].iter().copied().chain(spur).collect();
+                candidates.insert(Route {
+                    score: self.score(&states),
+                    states,
+                });
+            }
+            // every candidate differs from the routes found so far, since it
+            // makes a move they don't
+            let Some(next) = candidates.pop_first() else {
+                break;
+            };
+            routes.push(next);
+        }
+        routes
+    }
+}
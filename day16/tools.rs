@@ -0,0 +1,115 @@
+//! Tools for looking at the reindeer maze. `render` prints a puzzle input's
+//! maze with its best paths drawn in, and can also write it as a PPM image;
+//! `generate` writes a random maze and `bench` times the searches on one.
+
+use aoc::{Answer, Rng, Solution};
+use day16::generate;
+use day16::render;
+use day16::{Day16, Search};
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+use std::time::Instant;
+
+const USAGE: &str = "\
+usage: day16-tools render <input> [image.ppm] [scale]
+       day16-tools generate <width> <height> [loops] [seed]
+       day16-tools bench <width> <height> [loops] [seed]";
+
+/// A random maze from the `generate` and `bench` arguments: its size in
+/// cells, the percentage of walls knocked through (5 by default) and a seed.
+fn random_maze(args: &[&str]) -> Result<String, String> {
+    let number = |i: usize, name: &str, default: Option<u64>| match (args.get(i), default) {
+        (Some(arg), _) => arg
+            .parse()
+            .map_err(|_| format!("invalid {}: {}", name, arg)),
+        (None, Some(default)) => Ok(default),
+        (None, None) => Err(String::from(USAGE)),
+    };
+    let width = number(0, "width", None)? as usize;
+    let height = number(1, "height", None)? as usize;
+    let loops = number(2, "loop percentage", Some(5))?;
+    let seed = number(3, "seed", Some(0))?;
+    Ok(generate::maze(&mut Rng::new(seed), width, height, loops))
+}
+
+/// Times each search on a random maze, for part 1 alone and then part 2,
+/// checking that they agree.
+fn bench(args: &[&str]) -> Result<(), String> {
+    let input = random_maze(args)?;
+    let maze = Day16::parse(&input).map_err(|e| format!("invalid maze: {}", e))?;
+    let mut answers = Vec::new();
+    for search in [Search::Dijkstra, Search::AStar] {
+        let maze = maze.clone().with_search(search);
+        let start = Instant::now();
+        let score = maze.part1().into_answer().unwrap();
+        let part1 = start.elapsed();
+        let start = Instant::now();
+        let tiles = maze.part2().into_answer().unwrap();
+        let part2 = start.elapsed();
+        println!(
+            "{:<10} part 1 {:>10.3?}  part 2 {:>10.3?}  score {}, {} tiles",
+            format!("{:?}", search),
+            part1,
+            part2,
+            score.as_deref().unwrap_or("-"),
+            tiles.as_deref().unwrap_or("-")
+        );
+        answers.push((score, tiles));
+    }
+    if answers[0] != answers[1] {
+        return Err(String::from("the searches disagree"));
+    }
+    Ok(())
+}
+
+fn read_maze(path: &str) -> Result<Day16, String> {
+    let input = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    Day16::parse(&input).map_err(|e| format!("{}: invalid input: {}", path, e))
+}
+
+/// Prints the maze with the tiles on any best path marked `O` and one best
+/// path drawn with arrows, writing it as an image too if given a file.
+fn render(path: &str, image: Option<&str>, scale: Option<&str>) -> Result<(), String> {
+    let maze = read_maze(path)?;
+    let best = maze
+        .best_paths()
+        .ok_or_else(|| String::from("the end can't be reached"))?;
+    let map = maze.annotate(&best);
+    print!("{}", map);
+    println!("score {}, {} tiles", best.score, best.tiles.len());
+    if let Some(image) = image {
+        let scale = match scale {
+            Some(scale) => scale
+                .parse()
+                .map_err(|_| format!("invalid scale: {}", scale))?,
+            None => 4,
+        };
+        fs::write(image, render::ppm(&map, scale)).map_err(|e| format!("{}: {}", image, e))?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<_> = env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["render", path] => render(path, None, None),
+        ["render", path, image] => render(path, Some(image), None),
+        ["render", path, image, scale] => render(path, Some(image), Some(scale)),
+        ["generate", ref args @ ..] if !args.is_empty() && args.len() <= 4 => {
+            random_maze(args).map(|maze| print!("{}", maze))
+        }
+        ["bench", ref args @ ..] if !args.is_empty() && args.len() <= 4 => bench(args),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Conversion between programs and their mnemonics, one instruction per
+//! line, e.g. `bst A` for `2,4`. Combo operands 4, 5 and 6 are written as
+//! the registers `A`, `B` and `C` they read.
+
+use crate::cpu::{Opcode, Operand, Word};
+use aoc::ParseError;
+
+fn operand(opcode: Opcode, operand: Word) -> String {
+    match (opcode.operand(), operand) {
+        (Operand::Combo, 4) => String::from("A"),
+        (Operand::Combo, 5) => String::from("B"),
+        (Operand::Combo, 6) => String::from("C"),
+        _ => operand.to_string(),
+    }
+}
+
+/// Disassembles `program`. A trailing word without an operand, which is
+/// never executed, is written as a bare number.
+pub fn disassemble(program: &[Word]) -> String {
+    let mut text = String::new();
+    for instruction in program.chunks(2) {
+        match *instruction {
+            [opcode, operand] => match Opcode::decode(opcode) {
+                Some(opcode) => {
+                    text += &format!("{} {}\n", opcode.mnemonic(), self::operand(opcode, operand))
+                }
+                None => text += &format!("{} {}\n", opcode, operand),
+            },
+            [word] => text += &format!("{}\n", word),
+            _ => unreachable!(),
+        }
+    }
+    text
+}
+
+fn parse_word(input: &str, s: &str) -> Result<Word, ParseError> {
+    match s.parse::<Word>() {
+        Ok(word) if word <= 7 => Ok(word),
+        _ => Err(ParseError::new(input, s, "a 3-bit number")),
+    }
+}
+
+/// Assembles the output of `disassemble` back into a program. Blank lines and
+/// comments starting with `;` are skipped.
+pub fn assemble(input: &str) -> Result<Vec<Word>, ParseError> {
+    let mut program = Vec::new();
+    for line in input.lines() {
+        let line = line.split(';').next().unwrap().trim();
+        let fields: Vec<_> = line.split_whitespace().collect();
+        match fields[..] {
+            [] => {}
+            [word] => program.push(parse_word(input, word)?),
+            [mnemonic, operand] => {
+                let opcode = Opcode::ALL
+                    .into_iter()
+                    .find(|opcode| opcode.mnemonic() == mnemonic)
+                    .ok_or_else(|| ParseError::new(input, mnemonic, "an instruction mnemonic"))?;
+                let operand = match (opcode.operand(), operand) {
+                    (Operand::Combo, "A") => 4,
+                    (Operand::Combo, "B") => 5,
+                    (Operand::Combo, "C") => 6,
+                    _ => parse_word(input, operand)?,
+                };
+                program.extend([opcode.value(), operand]);
+            }
+            _ => {
+                return Err(ParseError::new(
+                    input,
+                    line,
+                    "an instruction 'mnemonic operand'",
+                ))
+            }
+        }
+    }
+    Ok(program)
+}
+
+/// Parses a program listed the way the puzzle input does, `s` being part of
+/// `input`.
+pub fn parse_program(input: &str, s: &str) -> Result<Vec<Word>, ParseError> {
+    s.split(',').map(|word| parse_word(input, word)).collect()
+}
+
+/// Formats a program the way the puzzle input lists it, e.g. `0,3,5,4,3,0`.
+pub fn format_program(program: &[Word]) -> String {
+    program
+        .iter()
+        .map(|word| word.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,238 @@
+//! The 3-bit computer: its instruction set and an emulator.
+
+use std::error::Error;
+use std::fmt;
+
+pub type Word = u64;
+
+/// `value >> shift`, with every bit shifted out for shifts of 64 or more.
+pub(crate) fn shift_right(value: Word, shift: Word) -> Word {
+    u32::try_from(shift)
+        .ok()
+        .and_then(|shift| value.checked_shr(shift))
+        .unwrap_or(0)
+}
+
+/// How an instruction reads its operand.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Operand {
+    Literal,
+    /// 0 to 3 as is, 4, 5 and 6 for registers A, B and C.
+    Combo,
+    /// Read but unused.
+    Ignored,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Opcode {
+    /// Every opcode, indexed by its value.
+    pub const ALL: [Opcode; 8] = [
+        Self::Adv,
+        Self::Bxl,
+        Self::Bst,
+        Self::Jnz,
+        Self::Bxc,
+        Self::Out,
+        Self::Bdv,
+        Self::Cdv,
+    ];
+
+    pub fn decode(word: Word) -> Option<Self> {
+        Self::ALL.get(usize::try_from(word).ok()?).copied()
+    }
+
+    pub fn value(&self) -> Word {
+        *self as Word
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Self::Adv => "adv",
+            Self::Bxl => "bxl",
+            Self::Bst => "bst",
+            Self::Jnz => "jnz",
+            Self::Bxc => "bxc",
+            Self::Out => "out",
+            Self::Bdv => "bdv",
+            Self::Cdv => "cdv",
+        }
+    }
+
+    pub fn operand(&self) -> Operand {
+        match self {
+            Self::Adv | Self::Bst | Self::Out | Self::Bdv | Self::Cdv => Operand::Combo,
+            Self::Bxl | Self::Jnz => Operand::Literal,
+            Self::Bxc => Operand::Ignored,
+        }
+    }
+}
+
+/// Why a program couldn't be run to its end.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum RunError {
+    InvalidOpcode {
+        pc: usize,
+        opcode: Word,
+    },
+    /// Combo operand 7 is reserved.
+    InvalidComboOperand {
+        pc: usize,
+        operand: Word,
+    },
+    /// The program didn't halt within the instruction budget.
+    BudgetExhausted {
+        budget: u64,
+    },
+    /// The CPU came back to a state it had already been in, so the program
+    /// would never halt.
+    Loop {
+        pc: usize,
+    },
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::InvalidOpcode { pc, opcode } => {
+                write!(f, "invalid opcode {} at {}", opcode, pc)
+            }
+            RunError::InvalidComboOperand { pc, operand } => {
+                write!(f, "invalid combo operand {} at {}", operand, pc)
+            }
+            RunError::BudgetExhausted { budget } => {
+                write!(f, "program didn't halt after {} instructions", budget)
+            }
+            RunError::Loop { pc } => write!(f, "program loops forever at {}", pc),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+/// The default number of instructions a program may execute.
+pub const DEFAULT_BUDGET: u64 = 1_000_000;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[allow(non_snake_case, clippy::upper_case_acronyms)]
+pub struct CPU {
+    pub A: Word,
+    pub B: Word,
+    pub C: Word,
+    pub pc: usize,
+}
+
+impl CPU {
+    fn combo_operand(&self, operand: Word) -> Option<Word> {
+        match operand {
+            0..=3 => Some(operand),
+            4 => Some(self.A),
+            5 => Some(self.B),
+            6 => Some(self.C),
+            _ => None,
+        }
+    }
+
+    pub fn execute(
+        &mut self,
+        opcode: Word,
+        operand: Word,
+        output: &mut Vec<Word>,
+    ) -> Result<(), RunError> {
+        let pc = self.pc;
+        let opcode = Opcode::decode(opcode).ok_or(RunError::InvalidOpcode { pc, opcode })?;
+        let operand = match opcode.operand() {
+            Operand::Combo => self
+                .combo_operand(operand)
+                .ok_or(RunError::InvalidComboOperand { pc, operand })?,
+            Operand::Literal | Operand::Ignored => operand,
+        };
+        self.pc += 2;
+        match opcode {
+            Opcode::Adv => self.A = shift_right(self.A, operand),
+            Opcode::Bxl => self.B ^= operand,
+            Opcode::Bst => self.B = operand & 0x7,
+            Opcode::Jnz => {
+                if self.A != 0 {
+                    self.pc = operand as usize;
+                }
+            }
+            Opcode::Bxc => self.B ^= self.C,
+            Opcode::Out => output.push(operand & 0x7),
+            Opcode::Bdv => self.B = shift_right(self.A, operand),
+            Opcode::Cdv => self.C = shift_right(self.A, operand),
+        }
+        Ok(())
+    }
+
+    /// Whether `pc` has run past the last instruction of `program`.
+    pub fn halted(&self, program: &[Word]) -> bool {
+        self.pc + 1 >= program.len()
+    }
+
+    /// Executes the instruction at `pc`, returning false if the program has
+    /// halted instead.
+    pub fn step(&mut self, program: &[Word], output: &mut Vec<Word>) -> Result<bool, RunError> {
+        if self.halted(program) {
+            return Ok(false);
+        }
+        self.execute(program[self.pc], program[self.pc + 1], output)?;
+        Ok(true)
+    }
+
+    /// Runs the program until it halts, executing at most `budget`
+    /// instructions.
+    pub fn run(
+        &mut self,
+        program: &[Word],
+        output: &mut Vec<Word>,
+        budget: u64,
+    ) -> Result<(), RunError> {
+        self.run_with(program, output, budget, |_| {})
+    }
+
+    /// Like `run`, calling `executed` with the position of every instruction
+    /// executed.
+    pub fn run_with(
+        &mut self,
+        program: &[Word],
+        output: &mut Vec<Word>,
+        budget: u64,
+        mut executed: impl FnMut(usize),
+    ) -> Result<(), RunError> {
+        // Brent's cycle detection: the state is saved after 1, 2, 4, ...
+        // instructions, and a loop shows up as a return to the saved state
+        // once the gap between saves is at least as long as the loop.
+        let mut saved = self.clone();
+        let mut next_save = 1;
+        for count in 1..=budget {
+            let pc = self.pc;
+            if !self.step(program, output)? {
+                return Ok(());
+            }
+            executed(pc);
+            if *self == saved {
+                return Err(RunError::Loop { pc: self.pc });
+            }
+            if count == next_save {
+                saved = self.clone();
+                next_save *= 2;
+            }
+        }
+        if self.halted(program) {
+            Ok(())
+        } else {
+            Err(RunError::BudgetExhausted { budget })
+        }
+    }
+}
@@ -1,88 +1,137 @@
-use std::io;
+pub mod asm;
+pub mod compiled;
+pub mod cpu;
+pub mod debugger;
+pub mod decompile;
+pub mod generate;
+pub mod report;
+pub mod symbolic;
 
-#[derive(Debug)]
-#[allow(non_snake_case)]
-struct CPU {
-    A: u32,
-    B: u32,
-    C: u32,
-    pc: usize,
+use aoc::{parse, Answer, Param, Params, ParseError, Solution};
+use compiled::Compiled;
+use cpu::{RunError, Word, CPU, DEFAULT_BUDGET};
+
+pub struct Day17 {
+    registers: Vec<Word>,
+    program: Vec<Word>,
+    compiled: Compiled,
+    /// Instructions a run of the program may execute.
+    budget: u64,
 }
 
-impl CPU {
-    fn combo_operand(&self, operand: u32) -> u32 {
-        match operand {
-            0..=3 => operand,
-            4 => self.A,
-            5 => self.B,
-            6 => self.C,
-            _ => panic!("invalid combo operand: {}", operand),
+impl Day17 {
+    /// A CPU with the puzzle's initial registers.
+    pub fn cpu(&self) -> CPU {
+        CPU {
+            A: self.registers[0],
+            B: self.registers[1],
+            C: self.registers[2],
+            pc: 0,
         }
     }
 
-    fn execute(&mut self, opcode: u32, operand: u32, output: &mut Vec<u32>) {
-        self.pc += 2;
-        match opcode {
-            0 => self.A >>= self.combo_operand(operand),
-            1 => self.B ^= operand,
-            2 => self.B = self.combo_operand(operand) & 0x7,
-            3 => {
-                if self.A != 0 {
-                    self.pc = operand as usize;
+    pub fn program(&self) -> &[Word] {
+        &self.program
+    }
+
+    fn output(&self, a: Word) -> Result<Vec<Word>, RunError> {
+        let mut cpu = CPU { A: a, ..self.cpu() };
+        let mut output = Vec::new();
+        self.compiled.run(&mut cpu, &mut output, self.budget)?;
+        Ok(output)
+    }
+
+    /// The smallest value of A that starts with the 3-bit groups of `a` and
+    /// makes the program output itself.
+    ///
+    /// Quine programs loop over their body, shift A right by 3 bits each
+    /// pass and stop once it is 0, so the last digits of the output only
+    /// depend on the highest bits of A, however many digits a pass outputs.
+    /// Each group is chosen so that the output is still the end of the
+    /// program, and the next one searched below it. Every value with one
+    /// more group is tried before any longer one, so the first quine found
+    /// is the smallest.
+    ///
+    /// `len` is how much `a` outputs. Every pass outputs something, so each
+    /// group has to make the output longer, which keeps the search as deep
+    /// as the program is long.
+    fn find_quine(&self, a: Word, len: usize) -> Result<Option<Word>, RunError> {
+        if a > Word::MAX >> 3 {
+            return Ok(None);
+        }
+        let candidates = (0..8)
+            .map(|bits| a << 3 | bits)
+            .map(|candidate| Ok((candidate, self.output(candidate)?)))
+            .collect::<Result<Vec<_>, RunError>>()?;
+        if let Some(&(quine, _)) = candidates
+            .iter()
+            .find(|(_, output)| *output == self.program)
+        {
+            return Ok(Some(quine));
+        }
+        for (candidate, output) in candidates {
+            // below 0 are the values on this level again
+            if candidate != 0
+                && output.len() > len
+                && output.len() < self.program.len()
+                && self.program.ends_with(&output)
+            {
+                if let Some(quine) = self.find_quine(candidate, output.len())? {
+                    return Ok(Some(quine));
                 }
             }
-            4 => self.B ^= self.C,
-            5 => output.push(self.combo_operand(operand) & 0x7),
-            6 => self.B = self.A >> self.combo_operand(operand),
-            7 => self.C = self.A >> self.combo_operand(operand),
-            _ => panic!("invalid opcode: {}", opcode),
         }
+        Ok(None)
     }
+}
 
-    fn run(&mut self, program: &[u32], output: &mut Vec<u32>) {
-        while self.pc + 1 < program.len() {
-            self.execute(program[self.pc], program[self.pc + 1], output);
-        }
+impl Solution for Day17 {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "budget",
+        default: DEFAULT_BUDGET,
+        description: "instructions a run of the program may execute",
+    }];
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &Params::new(Self::PARAMS))
     }
-}
 
-fn main() {
-    let input = io::read_to_string(io::stdin()).expect("error reading input");
-    let (registers, program) = input.split_once("\n\n").expect("error parsing input");
-    let registers: Vec<_> = registers
-        .lines()
-        .map(|register| {
-            let (_, value) = register.split_once(": ").expect("error parsing register");
-            value.parse::<u32>().expect("error parsing register value")
-        })
-        .collect();
-    let (_, program) = program.split_once(": ").expect("error parsing program");
-    let program: Vec<_> = program
-        .trim()
-        .split(',')
-        .map(|instruction| {
-            instruction
-                .parse::<u32>()
-                .expect("error parsing instruction")
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let (registers, program) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::end_of_input(input, "a blank line followed by the program")
+        })?;
+        let registers: Vec<_> = registers
+            .lines()
+            .map(|register| {
+                let (_, value) = register
+                    .split_once(": ")
+                    .ok_or_else(|| ParseError::new(input, register, "'Register X: value'"))?;
+                parse::<Word>(input, value, "a register value")
+            })
+            .collect::<Result<_, _>>()?;
+        let program = program.trim_end();
+        if registers.len() != 3 {
+            return Err(ParseError::new(input, program, "exactly 3 registers"));
+        }
+        let (_, program) = program
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new(input, program, "'Program: instructions'"))?;
+        let program = asm::parse_program(input, program)?;
+
+        Ok(Self {
+            registers,
+            compiled: Compiled::new(&program),
+            program,
+            budget: params.get("budget"),
         })
-        .collect();
+    }
 
-    assert!(registers.len() == 3);
-    let mut cpu = CPU {
-        A: registers[0],
-        B: registers[1],
-        C: registers[2],
-        pc: 0,
-    };
+    fn part1(&self) -> impl Answer {
+        self.output(self.registers[0])
+            .map(|output| Some(asm::format_program(&output)))
+    }
 
-    let mut output = Vec::new();
-    cpu.run(&program, &mut output);
-    println!(
-        "part 1: {}",
-        output
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<_>>()
-            .join(",")
-    );
+    fn part2(&self) -> impl Answer {
+        self.find_quine(0, 0)
+    }
 }
@@ -0,0 +1,144 @@
+//! Step-by-step execution of a program, for finding out why it misbehaves.
+
+use crate::asm;
+use crate::cpu::{RunError, Word, CPU, DEFAULT_BUDGET};
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Radix {
+    Decimal,
+    Octal,
+    Binary,
+}
+
+/// The registers of `cpu` written in `radix`, e.g. `A=0o17 B=0o0 C=0o3`.
+pub fn format_registers(cpu: &CPU, radix: Radix) -> String {
+    let format = |value: Word| match radix {
+        Radix::Decimal => value.to_string(),
+        Radix::Octal => format!("{:#o}", value),
+        Radix::Binary => format!("{:#b}", value),
+    };
+    format!(
+        "A={} B={} C={} pc={}",
+        format(cpu.A),
+        format(cpu.B),
+        format(cpu.C),
+        cpu.pc
+    )
+}
+
+/// An executed instruction with the registers after it.
+#[derive(Clone, Debug)]
+pub struct TraceEntry {
+    /// The instruction's position in the program.
+    pub pc: usize,
+    pub opcode: Word,
+    pub operand: Word,
+    pub after: CPU,
+    pub output: Option<Word>,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let instruction = asm::disassemble(&[self.opcode, self.operand]);
+        write!(
+            f,
+            "{:>4}  {:<6} {}",
+            self.pc,
+            instruction.trim_end(),
+            format_registers(&self.after, Radix::Decimal)
+        )?;
+        if let Some(value) = self.output {
+            write!(f, "  out {}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Why `Debugger::resume` stopped.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Stop {
+    Halted,
+    /// About to execute the instruction at a breakpoint.
+    Breakpoint(usize),
+    /// Just executed an `out` instruction.
+    Output(Word),
+    /// Couldn't execute the instruction at `pc`.
+    Error(RunError),
+}
+
+pub struct Debugger<'a> {
+    pub cpu: CPU,
+    program: &'a [Word],
+    pub output: Vec<Word>,
+    /// Every instruction executed so far.
+    pub trace: Vec<TraceEntry>,
+    pub breakpoints: BTreeSet<usize>,
+    pub break_on_output: bool,
+    /// Instructions `resume` may execute before giving up.
+    pub budget: u64,
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(cpu: CPU, program: &'a [Word]) -> Self {
+        Self {
+            cpu,
+            program,
+            output: Vec::new(),
+            trace: Vec::new(),
+            breakpoints: BTreeSet::new(),
+            break_on_output: false,
+            budget: DEFAULT_BUDGET,
+        }
+    }
+
+    pub fn program(&self) -> &'a [Word] {
+        self.program
+    }
+
+    pub fn halted(&self) -> bool {
+        self.cpu.halted(self.program)
+    }
+
+    /// Executes one instruction, or returns `None` if the program has halted.
+    pub fn step(&mut self) -> Result<Option<&TraceEntry>, RunError> {
+        let pc = self.cpu.pc;
+        let output_len = self.output.len();
+        if !self.cpu.step(self.program, &mut self.output)? {
+            return Ok(None);
+        }
+        self.trace.push(TraceEntry {
+            pc,
+            opcode: self.program[pc],
+            operand: self.program[pc + 1],
+            after: self.cpu.clone(),
+            output: self.output.get(output_len).copied(),
+        });
+        Ok(self.trace.last())
+    }
+
+    /// Runs until the program halts, reaches a breakpoint, or outputs a value
+    /// with `break_on_output` set. Always executes at least one instruction,
+    /// so resuming from a breakpoint moves past it.
+    pub fn resume(&mut self) -> Stop {
+        for executed in 0..self.budget {
+            if executed > 0 && self.breakpoints.contains(&self.cpu.pc) && !self.halted() {
+                return Stop::Breakpoint(self.cpu.pc);
+            }
+            match self.step().map(|entry| entry.map(|entry| entry.output)) {
+                Ok(None) => return Stop::Halted,
+                Ok(Some(Some(value))) if self.break_on_output => return Stop::Output(value),
+                Ok(Some(_)) => {}
+                Err(e) => return Stop::Error(e),
+            }
+        }
+        if self.halted() {
+            Stop::Halted
+        } else {
+            Stop::Error(RunError::BudgetExhausted {
+                budget: self.budget,
+            })
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Lifting of programs into pseudocode, with each output and register
+//! update written as one expression over the registers.
+
+use crate::cpu::{shift_right, Opcode, Word};
+use std::fmt;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Expr {
+    Const(Word),
+    /// A register's value at the start of the loop iteration.
+    Register(char),
+    Shr(Box<Expr>, Box<Expr>),
+    /// Terms xor-ed together, a constant term last.
+    Xor(Vec<Expr>),
+    /// The low 3 bits, `x & 7`.
+    Low3(Box<Expr>),
+}
+
+impl Expr {
+    fn xor_terms(self) -> Vec<Expr> {
+        match self {
+            Expr::Xor(terms) => terms,
+            Expr::Const(0) => Vec::new(),
+            e => vec![e],
+        }
+    }
+
+    /// `self ^ other`, merging constants and cancelling equal terms.
+    pub(crate) fn xor(self, other: Expr) -> Expr {
+        let mut constant = 0;
+        let mut terms: Vec<Expr> = Vec::new();
+        for term in self.xor_terms().into_iter().chain(other.xor_terms()) {
+            match term {
+                Expr::Const(c) => constant ^= c,
+                term => match terms.iter().position(|t| *t == term) {
+                    Some(i) => {
+                        terms.remove(i);
+                    }
+                    None => terms.push(term),
+                },
+            }
+        }
+        if constant != 0 {
+            terms.push(Expr::Const(constant));
+        }
+        match terms.len() {
+            0 => Expr::Const(0),
+            1 => terms.pop().unwrap(),
+            _ => Expr::Xor(terms),
+        }
+    }
+
+    pub(crate) fn shr(self, shift: Expr) -> Expr {
+        match (self, shift) {
+            (Expr::Const(value), Expr::Const(shift)) => Expr::Const(shift_right(value, shift)),
+            (value, Expr::Const(0)) => value,
+            (Expr::Shr(value, inner), Expr::Const(shift)) => match *inner {
+                Expr::Const(inner) => value.shr(Expr::Const(inner.saturating_add(shift))),
+                inner => Expr::Shr(
+                    Box::new(Expr::Shr(value, Box::new(inner))),
+                    Box::new(Expr::Const(shift)),
+                ),
+            },
+            (Expr::Const(0), _) => Expr::Const(0),
+            (value, shift) => Expr::Shr(Box::new(value), Box::new(shift)),
+        }
+    }
+
+    /// `self & 7`. Masks inside the terms of a xor are dropped, since the
+    /// mask applies to all of them.
+    pub(crate) fn low3(self) -> Expr {
+        match self {
+            Expr::Const(c) => Expr::Const(c & 7),
+            Expr::Low3(e) => Expr::Low3(e),
+            Expr::Xor(terms) => {
+                let e = terms.into_iter().fold(Expr::Const(0), |acc, term| {
+                    acc.xor(match term {
+                        Expr::Low3(e) => *e,
+                        Expr::Const(c) => Expr::Const(c & 7),
+                        term => term,
+                    })
+                });
+                match e {
+                    Expr::Const(c) => Expr::Const(c),
+                    e => Expr::Low3(Box::new(e)),
+                }
+            }
+            e => Expr::Low3(Box::new(e)),
+        }
+    }
+
+    /// The expression with the registers replaced by `a`, `b` and `c`.
+    pub fn substitute(&self, a: &Expr, b: &Expr, c: &Expr) -> Expr {
+        match self {
+            Expr::Const(value) => Expr::Const(*value),
+            Expr::Register('A') => a.clone(),
+            Expr::Register('B') => b.clone(),
+            Expr::Register(_) => c.clone(),
+            Expr::Shr(value, shift) => value.substitute(a, b, c).shr(shift.substitute(a, b, c)),
+            Expr::Xor(terms) => terms.iter().fold(Expr::Const(0), |acc, term| {
+                acc.xor(term.substitute(a, b, c))
+            }),
+            Expr::Low3(e) => e.substitute(a, b, c).low3(),
+        }
+    }
+
+    /// The value of the expression for the given register values.
+    pub fn eval(&self, a: Word, b: Word, c: Word) -> Word {
+        match self {
+            Expr::Const(value) => *value,
+            Expr::Register('A') => a,
+            Expr::Register('B') => b,
+            Expr::Register(_) => c,
+            Expr::Shr(value, shift) => shift_right(value.eval(a, b, c), shift.eval(a, b, c)),
+            Expr::Xor(terms) => terms.iter().fold(0, |acc, term| acc ^ term.eval(a, b, c)),
+            Expr::Low3(e) => e.eval(a, b, c) & 7,
+        }
+    }
+
+    fn is_atom(&self) -> bool {
+        matches!(self, Expr::Const(_) | Expr::Register(_))
+    }
+}
+
+/// Writes `e`, in parentheses unless it is a constant or a register.
+struct Operand<'a>(&'a Expr);
+
+impl fmt::Display for Operand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_atom() {
+            write!(f, "{}", self.0)
+        } else {
+            write!(f, "({})", self.0)
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Const(c) => write!(f, "{}", c),
+            Expr::Register(r) => write!(f, "{}", r),
+            Expr::Shr(value, shift) => write!(f, "{} >> {}", Operand(value), Operand(shift)),
+            Expr::Xor(terms) => {
+                for (i, term) in terms.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ^ ")?;
+                    }
+                    write!(f, "{}", Operand(term))?;
+                }
+                Ok(())
+            }
+            Expr::Low3(e) => write!(f, "{} & 7", Operand(e)),
+        }
+    }
+}
+
+/// A decompiled program: its outputs in order, then the registers it leaves,
+/// all in terms of the registers at the start. With `loops` set, the whole
+/// body repeats while A isn't 0.
+#[derive(Clone, Debug)]
+pub struct Decompiled {
+    pub outputs: Vec<Expr>,
+    pub a: Expr,
+    pub b: Expr,
+    pub c: Expr,
+    pub loops: bool,
+}
+
+impl fmt::Display for Decompiled {
+    /// Writes the outputs, then the registers the code changes as one
+    /// simultaneous assignment, since each is in terms of the registers
+    /// before any of them changed.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let indent = if self.loops { "    " } else { "" };
+        if self.loops {
+            writeln!(f, "do {{")?;
+        }
+        for output in &self.outputs {
+            writeln!(f, "{}out({})", indent, output)?;
+        }
+        let (names, values): (Vec<_>, Vec<_>) = [('A', &self.a), ('B', &self.b), ('C', &self.c)]
+            .into_iter()
+            .filter(|&(name, e)| *e != Expr::Register(name))
+            .map(|(name, e)| (name.to_string(), e.to_string()))
+            .unzip();
+        match names.len() {
+            0 => {}
+            1 => writeln!(f, "{}{} = {}", indent, names[0], values[0])?,
+            _ => writeln!(
+                f,
+                "{}({}) = ({})",
+                indent,
+                names.join(", "),
+                values.join(", ")
+            )?,
+        }
+        if self.loops {
+            writeln!(f, "}} while A != 0")?;
+        }
+        Ok(())
+    }
+}
+
+/// Decompiles a program made of straight-line code, optionally followed by
+/// a `jnz 0` that loops back over all of it.
+pub fn decompile(program: &[Word]) -> Result<Decompiled, String> {
+    let mut instructions: Vec<_> = program.chunks_exact(2).collect();
+    let loops = instructions.last() == Some(&&[Opcode::Jnz.value(), 0][..]);
+    if loops {
+        instructions.pop();
+    }
+
+    let mut a = Expr::Register('A');
+    let mut b = Expr::Register('B');
+    let mut c = Expr::Register('C');
+    let mut outputs = Vec::new();
+    for (i, instruction) in instructions.iter().enumerate() {
+        let [opcode, operand] = **instruction else {
+            unreachable!()
+        };
+        let pc = i * 2;
+        let combo = || match operand {
+            0..=3 => Ok(Expr::Const(operand)),
+            4 => Ok(a.clone()),
+            5 => Ok(b.clone()),
+            6 => Ok(c.clone()),
+            _ => Err(format!("invalid combo operand {} at {}", operand, pc)),
+        };
+        match Opcode::decode(opcode) {
+            Some(Opcode::Adv) => a = a.clone().shr(combo()?),
+            Some(Opcode::Bxl) => b = b.xor(Expr::Const(operand)),
+            Some(Opcode::Bst) => b = combo()?.low3(),
+            Some(Opcode::Jnz) => return Err(format!("unsupported jump at {}", pc)),
+            Some(Opcode::Bxc) => b = b.xor(c.clone()),
+            Some(Opcode::Out) => outputs.push(combo()?.low3()),
+            Some(Opcode::Bdv) => b = a.clone().shr(combo()?),
+            Some(Opcode::Cdv) => c = a.clone().shr(combo()?),
+            None => return Err(format!("invalid opcode {} at {}", opcode, pc)),
+        }
+    }
+    Ok(Decompiled {
+        outputs,
+        a,
+        b,
+        c,
+        loops,
+    })
+}
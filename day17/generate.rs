@@ -0,0 +1,76 @@
+//! Random valid programs, for checking the CPU, the assembler and the solvers
+//! against each other.
+
+use crate::cpu::{Opcode, Operand, Word};
+use aoc::Rng;
+
+/// The combo operands for A, B and C.
+const A: Word = 4;
+const B: Word = 5;
+const C: Word = 6;
+
+/// A random operand that is valid for `opcode`.
+fn operand(rng: &mut Rng, opcode: Opcode) -> Word {
+    match opcode.operand() {
+        Operand::Combo => rng.below(7),
+        Operand::Literal | Operand::Ignored => rng.below(8),
+    }
+}
+
+/// A program of `instructions` random instructions, all of which can be
+/// executed. Jumps may land on odd positions or past the end, and the
+/// program may well never halt.
+pub fn random_program(rng: &mut Rng, instructions: usize) -> Vec<Word> {
+    let mut program = Vec::new();
+    for _ in 0..instructions {
+        let opcode = *rng.pick(&Opcode::ALL);
+        program.extend([opcode.value(), operand(rng, opcode)]);
+    }
+    program
+}
+
+/// A program shaped like the puzzle's: `instructions` random instructions
+/// that output at least once, plus an `adv 3` somewhere among them, looped
+/// over with `jnz 0`. B and C are always set in the body before
+/// they are read, so each pass only depends on A, which is what the part 2
+/// solvers rely on.
+pub fn loop_program(rng: &mut Rng, instructions: usize) -> Vec<Word> {
+    let instructions = instructions.max(1);
+    let mut body = Vec::new();
+    let (mut b_set, mut c_set) = (false, false);
+    let mut outputs = false;
+    for i in 0..instructions {
+        let mut combo = vec![0, 1, 2, 3, A];
+        combo.extend(b_set.then_some(B));
+        combo.extend(c_set.then_some(C));
+        let combo = *rng.pick(&combo);
+
+        let mut opcodes = vec![Opcode::Bst, Opcode::Out, Opcode::Bdv, Opcode::Cdv];
+        if b_set {
+            opcodes.push(Opcode::Bxl);
+        }
+        if b_set && c_set {
+            opcodes.push(Opcode::Bxc);
+        }
+        let opcode = if i == instructions - 1 && !outputs {
+            Opcode::Out
+        } else {
+            *rng.pick(&opcodes)
+        };
+        let operand = match opcode {
+            Opcode::Bxl | Opcode::Bxc => rng.below(8),
+            _ => combo,
+        };
+        match opcode {
+            Opcode::Bst | Opcode::Bdv => b_set = true,
+            Opcode::Cdv => c_set = true,
+            Opcode::Out => outputs = true,
+            _ => {}
+        }
+        body.push([opcode.value(), operand]);
+    }
+    let shift = rng.below(body.len() as Word + 1) as usize;
+    body.insert(shift, [Opcode::Adv.value(), 3]);
+    body.push([Opcode::Jnz.value(), 0]);
+    body.concat()
+}
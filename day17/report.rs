@@ -0,0 +1,127 @@
+//! A summary of a whole run of a program: what it output, where it ended up
+//! and which instructions it spent its time in.
+
+use crate::asm;
+use crate::cpu::{Opcode, RunError, Word, CPU};
+use crate::debugger::{format_registers, Radix};
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub output: Vec<Word>,
+    /// The CPU once the run stopped.
+    pub cpu: CPU,
+    /// Why the run stopped before the program halted, if it did.
+    pub error: Option<RunError>,
+    /// The number of instructions executed.
+    pub executed: u64,
+    /// How often each opcode was executed, indexed by its value.
+    pub opcodes: [u64; 8],
+    /// How often the instruction at each position was executed.
+    pub hits: Vec<u64>,
+    program: Vec<Word>,
+}
+
+impl Report {
+    /// Runs `program` on `cpu` like `CPU::run`, counting every instruction
+    /// executed.
+    pub fn run(mut cpu: CPU, program: &[Word], budget: u64) -> Self {
+        let mut output = Vec::new();
+        let mut hits = vec![0; program.len()];
+        let error = cpu
+            .run_with(program, &mut output, budget, |pc| hits[pc] += 1)
+            .err();
+        let mut opcodes = [0; 8];
+        for (pc, &count) in hits.iter().enumerate() {
+            // Executed instructions always have a valid opcode.
+            opcodes[program[pc] as usize] += count;
+        }
+        Self {
+            output,
+            cpu,
+            error,
+            executed: hits.iter().sum(),
+            opcodes,
+            hits,
+            program: program.to_vec(),
+        }
+    }
+
+    /// The report as a single-line JSON object:
+    /// `{"output":[..],"a":..,"b":..,"c":..,"pc":..,"error":null,"executed":..,"opcodes":{"adv":..,..},"hits":[..]}`,
+    /// with `hits` indexed by position in the program.
+    pub fn json(&self) -> String {
+        let list = |values: &[Word]| {
+            values
+                .iter()
+                .map(Word::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let opcodes = Opcode::ALL
+            .iter()
+            .map(|opcode| {
+                format!(
+                    "\"{}\":{}",
+                    opcode.mnemonic(),
+                    self.opcodes[opcode.value() as usize]
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        // Error messages are plain ASCII without quotes or backslashes.
+        let error = self
+            .error
+            .map_or(String::from("null"), |e| format!("\"{}\"", e));
+        format!(
+            "{{\"output\":[{}],\"a\":{},\"b\":{},\"c\":{},\"pc\":{},\"error\":{},\"executed\":{},\"opcodes\":{{{}}},\"hits\":[{}]}}",
+            list(&self.output),
+            self.cpu.A,
+            self.cpu.B,
+            self.cpu.C,
+            self.cpu.pc,
+            error,
+            self.executed,
+            opcodes,
+            list(&self.hits)
+        )
+    }
+}
+
+impl fmt::Display for Report {
+    /// Lists the opcodes and instructions that were executed at least once,
+    /// with how often.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "output:    {}", asm::format_program(&self.output))?;
+        writeln!(
+            f,
+            "registers: {}",
+            format_registers(&self.cpu, Radix::Decimal)
+        )?;
+        if let Some(e) = self.error {
+            writeln!(f, "error:     {}", e)?;
+        }
+        writeln!(f, "executed:  {} instructions", self.executed)?;
+        writeln!(f, "opcodes:")?;
+        for opcode in Opcode::ALL {
+            let count = self.opcodes[opcode.value() as usize];
+            if count > 0 {
+                writeln!(f, "  {}  {:>10}", opcode.mnemonic(), count)?;
+            }
+        }
+        writeln!(f, "instructions:")?;
+        for (pc, &count) in self.hits.iter().enumerate() {
+            if count > 0 {
+                let instruction = asm::disassemble(&self.program[pc..pc + 2]);
+                writeln!(
+                    f,
+                    "  {:>4}  {:<6} {:>10}",
+                    pc,
+                    instruction.trim_end(),
+                    count
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
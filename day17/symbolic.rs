@@ -0,0 +1,196 @@
+//! Symbolic execution of programs with an unknown register A, to find every
+//! value of A that makes a program output a given sequence.
+//!
+//! The loop body is decompiled and unrolled once per iteration the output
+//! needs, giving an expression over the bits of A for every output digit.
+//! A is then built bit by bit from the highest, abandoning an assignment as
+//! soon as the bits known so far contradict one of the digits or the loop
+//! stopping too early or too late.
+
+use crate::cpu::{shift_right, Word};
+use crate::decompile::{decompile, Expr};
+
+/// A value with only some of its bits known. Unknown bits of `value` are 0.
+#[derive(Copy, Clone, Debug)]
+struct Partial {
+    known: Word,
+    value: Word,
+}
+
+impl Partial {
+    fn known(value: Word) -> Self {
+        Self {
+            known: Word::MAX,
+            value,
+        }
+    }
+
+    fn is_known(&self) -> bool {
+        self.known == Word::MAX
+    }
+}
+
+/// The bits of `e` that follow from the known bits of A.
+fn eval(e: &Expr, a: Partial, b: Word, c: Word) -> Partial {
+    match e {
+        Expr::Const(value) => Partial::known(*value),
+        Expr::Register('A') => a,
+        Expr::Register('B') => Partial::known(b),
+        Expr::Register(_) => Partial::known(c),
+        Expr::Shr(value, shift) => {
+            let value = eval(value, a, b, c);
+            let shift = eval(shift, a, b, c);
+            if shift.is_known() {
+                // the bits shifted in at the top are known zeros
+                Partial {
+                    known: shift_right(value.known, shift.value)
+                        | !shift_right(Word::MAX, shift.value),
+                    value: shift_right(value.value, shift.value),
+                }
+            } else if value.is_known() && value.value == 0 {
+                Partial::known(0)
+            } else {
+                Partial { known: 0, value: 0 }
+            }
+        }
+        Expr::Xor(terms) => terms.iter().fold(Partial::known(0), |acc, term| {
+            let term = eval(term, a, b, c);
+            Partial {
+                known: acc.known & term.known,
+                value: (acc.value ^ term.value) & acc.known & term.known,
+            }
+        }),
+        Expr::Low3(e) => {
+            let e = eval(e, a, b, c);
+            Partial {
+                known: e.known | !7,
+                value: e.value & 7,
+            }
+        }
+    }
+}
+
+enum Constraint {
+    /// The expression has this value.
+    Equals(Expr, Word),
+    NonZero(Expr),
+}
+
+impl Constraint {
+    /// Whether the known bits of A already break the constraint.
+    fn violated(&self, a: Partial, b: Word, c: Word) -> bool {
+        match self {
+            Constraint::Equals(e, expected) => {
+                let value = eval(e, a, b, c);
+                (value.value ^ expected) & value.known != 0
+            }
+            Constraint::NonZero(e) => {
+                let value = eval(e, a, b, c);
+                value.is_known() && value.value == 0
+            }
+        }
+    }
+}
+
+/// The constraints on A for the program to output `target`, then halt.
+fn constraints(program: &[Word], target: &[Word]) -> Result<Vec<Constraint>, String> {
+    let body = decompile(program)?;
+    let per_iteration = body.outputs.len();
+    let iterations = match (body.loops, per_iteration) {
+        (false, _) => 1,
+        (true, 0) => return Err(String::from("the loop doesn't output anything")),
+        (true, n) if target.len().is_multiple_of(n) => target.len() / n,
+        (true, n) => {
+            return Err(format!(
+                "the loop outputs {} digits at a time, which can't make {}",
+                n,
+                target.len()
+            ))
+        }
+    };
+    if !body.loops && per_iteration != target.len() {
+        // a constraint no value of A can meet
+        return Ok(vec![Constraint::Equals(Expr::Const(0), 1)]);
+    }
+
+    let mut constraints = Vec::new();
+    let mut registers = [
+        Expr::Register('A'),
+        Expr::Register('B'),
+        Expr::Register('C'),
+    ];
+    let mut digits = target.iter();
+    for i in 0..iterations {
+        let [a, b, c] = &registers;
+        for output in &body.outputs {
+            let digit = *digits.next().unwrap();
+            constraints.push(Constraint::Equals(output.substitute(a, b, c), digit));
+        }
+        registers = [&body.a, &body.b, &body.c].map(|e| e.substitute(a, b, c));
+        if body.loops {
+            let a = registers[0].clone();
+            constraints.push(if i + 1 < iterations {
+                Constraint::NonZero(a)
+            } else {
+                Constraint::Equals(a, 0)
+            });
+        }
+    }
+    Ok(constraints)
+}
+
+/// Assigns the bits of A below `bits`, from the highest down and 0 before
+/// 1, so solutions are found in increasing order.
+fn search(
+    a: Partial,
+    bits: u32,
+    b: Word,
+    c: Word,
+    constraints: &[Constraint],
+    solutions: &mut Vec<Word>,
+    limit: usize,
+) {
+    if solutions.len() >= limit
+        || constraints
+            .iter()
+            .any(|constraint| constraint.violated(a, b, c))
+    {
+        return;
+    }
+    if bits == 0 {
+        solutions.push(a.value);
+        return;
+    }
+    let bit = bits - 1;
+    for value in [0, 1] {
+        let a = Partial {
+            known: a.known | 1 << bit,
+            value: a.value | value << bit,
+        };
+        search(a, bit, b, c, constraints, solutions, limit);
+    }
+}
+
+/// The `limit` smallest values of A, in increasing order, for which the
+/// program outputs `target` and halts, given the initial values of B and C.
+/// Fails for programs that aren't a straight line or a single loop.
+pub fn solve(
+    program: &[Word],
+    b: Word,
+    c: Word,
+    target: &[Word],
+    limit: usize,
+) -> Result<Vec<Word>, String> {
+    let constraints = constraints(program, target)?;
+    let mut solutions = Vec::new();
+    search(
+        Partial { known: 0, value: 0 },
+        Word::BITS,
+        b,
+        c,
+        &constraints,
+        &mut solutions,
+        limit,
+    );
+    Ok(solutions)
+}
@@ -0,0 +1,342 @@
+//! Tools for writing and inspecting programs of the 3-bit computer. `disasm`
+//! and `decompile` read a program from stdin, either as a puzzle input or as a bare
+//! comma-separated list; `trace`, `debug` and `report` run a puzzle input,
+//! optionally with another value in register A; `speed` times the CPU against
+//! the compiled program over a range of values of A; `generate` writes a
+//! random puzzle-shaped input.
+
+use aoc::{Rng, Solution};
+use day17::asm;
+use day17::compiled::Compiled;
+use day17::cpu::{RunError, Word, CPU, DEFAULT_BUDGET};
+use day17::debugger::{format_registers, Debugger, Radix, Stop};
+use day17::decompile;
+use day17::generate::loop_program;
+use day17::report::Report;
+use day17::symbolic;
+use day17::Day17;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Instant;
+
+const USAGE: &str = "\
+usage: day17-tools disasm < program
+       day17-tools asm < mnemonics
+       day17-tools decompile < program
+       day17-tools quines <input> [limit]
+       day17-tools trace <input> [A]
+       day17-tools debug <input> [A]
+       day17-tools report <input> [A] [--json]
+       day17-tools speed <input> [count]
+       day17-tools generate <seed> [instructions]";
+
+/// The program of a puzzle input, or `input` itself if it has no
+/// `Program: ` line.
+fn read_program(input: &str) -> Result<Vec<Word>, String> {
+    let program = input
+        .lines()
+        .find_map(|line| line.strip_prefix("Program: "))
+        .unwrap_or(input.trim());
+    asm::parse_program(input, program).map_err(|e| format!("invalid program: {}", e))
+}
+
+fn disasm(input: &str) -> Result<(), String> {
+    print!("{}", asm::disassemble(&read_program(input)?));
+    Ok(())
+}
+
+fn asm(input: &str) -> Result<(), String> {
+    let program = asm::assemble(input).map_err(|e| format!("invalid assembly: {}", e))?;
+    println!("{}", asm::format_program(&program));
+    Ok(())
+}
+
+fn decompile(input: &str) -> Result<(), String> {
+    print!("{}", decompile::decompile(&read_program(input)?)?);
+    Ok(())
+}
+
+fn read_stdin() -> Result<String, String> {
+    io::read_to_string(io::stdin()).map_err(|e| format!("error reading input: {}", e))
+}
+
+/// The puzzle input in `path`, with register A replaced by `a` if given.
+fn read_puzzle(path: &str, a: Option<&str>) -> Result<(Day17, CPU), String> {
+    let input = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let day = Day17::parse(&input).map_err(|e| format!("{}: invalid input: {}", path, e))?;
+    let mut cpu = day.cpu();
+    if let Some(a) = a {
+        cpu.A = a
+            .parse()
+            .map_err(|_| format!("invalid value for A: {}", a))?;
+    }
+    Ok((day, cpu))
+}
+
+/// Prints the smallest values of A for which the program outputs itself.
+fn quines(path: &str, limit: Option<&str>) -> Result<(), String> {
+    let (day, cpu) = read_puzzle(path, None)?;
+    let limit = match limit {
+        Some(limit) => limit
+            .parse()
+            .map_err(|_| format!("invalid limit: {}", limit))?,
+        None => 10,
+    };
+    for a in symbolic::solve(day.program(), cpu.B, cpu.C, day.program(), limit)? {
+        println!("{}", a);
+    }
+    Ok(())
+}
+
+fn trace(path: &str, a: Option<&str>) -> Result<(), String> {
+    let (day, cpu) = read_puzzle(path, a)?;
+    let mut debugger = Debugger::new(cpu, day.program());
+    for _ in 0..DEFAULT_BUDGET {
+        match debugger.step().map_err(|e| e.to_string())? {
+            Some(entry) => println!("{}", entry),
+            None => {
+                println!("output: {}", asm::format_program(&debugger.output));
+                return Ok(());
+            }
+        }
+    }
+    Err(RunError::BudgetExhausted {
+        budget: DEFAULT_BUDGET,
+    }
+    .to_string())
+}
+
+/// Runs the program for A = 0..count on the CPU, the compiled program and
+/// the compiled program on every core, and prints how long each took.
+fn speed(path: &str, count: Option<&str>) -> Result<(), String> {
+    let (day, cpu) = read_puzzle(path, None)?;
+    let count: Word = match count {
+        Some(count) => count
+            .parse()
+            .map_err(|_| format!("invalid count: {}", count))?,
+        None => 100_000,
+    };
+    let a_values: Vec<_> = (0..count).collect();
+
+    let start = Instant::now();
+    let expected: Vec<_> = a_values
+        .iter()
+        .map(|&a| {
+            let mut cpu = CPU {
+                A: a,
+                ..cpu.clone()
+            };
+            let mut output = Vec::new();
+            cpu.run(day.program(), &mut output, DEFAULT_BUDGET)
+                .map(|()| output)
+        })
+        .collect();
+    let cpu_time = start.elapsed();
+    println!("cpu:      {:>10.3?}", cpu_time);
+
+    let compiled = Compiled::new(day.program());
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    for (name, threads) in [("compiled", 1), ("parallel", threads)] {
+        let start = Instant::now();
+        let outputs = compiled.run_batch(&a_values, cpu.B, cpu.C, DEFAULT_BUDGET, threads);
+        let time = start.elapsed();
+        if outputs != expected {
+            return Err(format!("{} output differs from the cpu", name));
+        }
+        println!(
+            "{}: {:>10.3?} ({:.1}x, {} threads)",
+            name,
+            time,
+            cpu_time.as_secs_f64() / time.as_secs_f64(),
+            threads
+        );
+    }
+    Ok(())
+}
+
+fn report(path: &str, a: Option<&str>, json: bool) -> Result<(), String> {
+    let (day, cpu) = read_puzzle(path, a)?;
+    let report = Report::run(cpu, day.program(), DEFAULT_BUDGET);
+    if json {
+        println!("{}", report.json());
+    } else {
+        print!("{}", report);
+    }
+    Ok(())
+}
+
+/// Prints an input whose program is a random loop like the puzzle's, with
+/// A set to a random value.
+fn generate(seed: &str, instructions: Option<&str>) -> Result<(), String> {
+    let seed = seed
+        .parse()
+        .map_err(|_| format!("invalid seed: {}", seed))?;
+    let instructions = match instructions {
+        Some(n) => n
+            .parse()
+            .map_err(|_| format!("invalid instruction count: {}", n))?,
+        None => 6,
+    };
+    let mut rng = Rng::new(seed);
+    let program = loop_program(&mut rng, instructions);
+    println!("Register A: {}", rng.below(1 << 48));
+    println!("Register B: 0");
+    println!("Register C: 0");
+    println!();
+    println!("Program: {}", asm::format_program(&program));
+    Ok(())
+}
+
+const DEBUG_HELP: &str = "\
+s, step [n]          execute n instructions (1 by default)
+c, continue          run to a breakpoint, an output with `o` set, or the end
+b, break [pc]        toggle a breakpoint at pc, or list the breakpoints
+o, output            toggle stopping after every `out` instruction
+r, registers [d|o|b] print the registers in decimal, octal or binary
+t, trace             print every instruction executed so far
+p, print             print the output so far
+l, list              disassemble the program, marking pc
+q, quit";
+
+/// Runs one debugger command, returning false to quit.
+fn debug_command(debugger: &mut Debugger, line: &str) -> Result<bool, String> {
+    let fields: Vec<_> = line.split_whitespace().collect();
+    match fields[..] {
+        [] => {}
+        ["s" | "step", ref n @ ..] => {
+            let n = match n {
+                [] => 1,
+                [n] => n.parse().map_err(|_| format!("invalid count: {}", n))?,
+                _ => return Err(String::from("usage: step [n]")),
+            };
+            for _ in 0..n {
+                match debugger.step() {
+                    Ok(Some(entry)) => println!("{}", entry),
+                    Ok(None) => {
+                        println!("halted");
+                        break;
+                    }
+                    Err(e) => {
+                        println!("error: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+        ["c" | "continue"] => match debugger.resume() {
+            Stop::Halted => println!("halted"),
+            Stop::Breakpoint(pc) => println!("breakpoint at {}", pc),
+            Stop::Output(value) => println!("output {}", value),
+            Stop::Error(e) => println!("error: {}", e),
+        },
+        ["b" | "break"] => {
+            for pc in &debugger.breakpoints {
+                println!("breakpoint at {}", pc);
+            }
+        }
+        ["b" | "break", pc] => {
+            let pc = pc.parse().map_err(|_| format!("invalid pc: {}", pc))?;
+            if debugger.breakpoints.remove(&pc) {
+                println!("removed breakpoint at {}", pc);
+            } else {
+                debugger.breakpoints.insert(pc);
+                println!("breakpoint at {}", pc);
+            }
+        }
+        ["o" | "output"] => {
+            debugger.break_on_output = !debugger.break_on_output;
+            println!(
+                "{} on output",
+                if debugger.break_on_output {
+                    "stopping"
+                } else {
+                    "not stopping"
+                }
+            );
+        }
+        ["r" | "registers", ref radix @ ..] => {
+            let radix = match radix {
+                [] | ["d"] => Radix::Decimal,
+                ["o"] => Radix::Octal,
+                ["b"] => Radix::Binary,
+                _ => return Err(String::from("usage: registers [d|o|b]")),
+            };
+            println!("{}", format_registers(&debugger.cpu, radix));
+        }
+        ["t" | "trace"] => {
+            for entry in &debugger.trace {
+                println!("{}", entry);
+            }
+        }
+        ["p" | "print"] => println!("{}", asm::format_program(&debugger.output)),
+        ["l" | "list"] => {
+            let text = asm::disassemble(debugger.program());
+            for (i, instruction) in text.lines().enumerate() {
+                let marker = if i * 2 == debugger.cpu.pc { "=>" } else { "" };
+                println!("{:>2} {:>4}  {}", marker, i * 2, instruction);
+            }
+        }
+        ["h" | "help"] => println!("{}", DEBUG_HELP),
+        ["q" | "quit"] => return Ok(false),
+        _ => return Err(format!("unknown command: {} (try 'help')", line.trim())),
+    }
+    Ok(true)
+}
+
+fn debug(path: &str, a: Option<&str>) -> Result<(), String> {
+    let (day, cpu) = read_puzzle(path, a)?;
+    let mut debugger = Debugger::new(cpu, day.program());
+    println!("{}", format_registers(&debugger.cpu, Radix::Decimal));
+    let mut lines = io::stdin().lines();
+    loop {
+        print!("(day17) ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        let line = line.map_err(|e| format!("error reading command: {}", e))?;
+        match debug_command(&mut debugger, &line) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<_> = env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["disasm"] => read_stdin().and_then(|input| disasm(&input)),
+        ["asm"] => read_stdin().and_then(|input| asm(&input)),
+        ["decompile"] => read_stdin().and_then(|input| decompile(&input)),
+        ["quines", path] => quines(path, None),
+        ["quines", path, limit] => quines(path, Some(limit)),
+        ["trace", path] => trace(path, None),
+        ["trace", path, a] => trace(path, Some(a)),
+        ["debug", path] => debug(path, None),
+        ["debug", path, a] => debug(path, Some(a)),
+        ["report", path] => report(path, None, false),
+        ["report", path, "--json"] => report(path, None, true),
+        ["report", path, a] => report(path, Some(a), false),
+        ["report", path, a, "--json"] => report(path, Some(a), true),
+        ["generate", seed] => generate(seed, None),
+        ["generate", seed, instructions] => generate(seed, Some(instructions)),
+        ["speed", path] => speed(path, None),
+        ["speed", path, count] => speed(path, Some(count)),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
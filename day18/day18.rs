@@ -1,39 +1,8 @@
+use aoc::{Answer, Grid, Param, Params, ParseError, Point, Solution};
 use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::io;
-use std::str::FromStr;
 
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-#[derive(Debug)]
-struct ParsePointError;
-
-impl FromStr for Point {
-    type Err = ParsePointError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').ok_or(ParsePointError)?;
-        Ok(Point {
-            x: x.parse::<isize>().map_err(|_| ParsePointError)?,
-            y: y.parse::<isize>().map_err(|_| ParsePointError)?,
-        })
-    }
-}
-
-fn within_bounds(p: &Point, mem_size: usize) -> bool {
-    p.x >= 0 && (p.x as usize) <= mem_size && p.y >= 0 && (p.y as usize) <= mem_size
-}
-
-fn min_steps(
-    start: Point,
-    end: Point,
-    mem_size: usize,
-    corrupted: &HashSet<Point>,
-) -> Option<usize> {
+fn min_steps(start: Point, end: Point, corrupted: &Grid<bool>) -> Option<usize> {
     let mut visited = HashSet::new();
     let mut q = VecDeque::new();
 
@@ -44,16 +13,8 @@ fn min_steps(
         if pos == end {
             return Some(steps);
         }
-        let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-        for (dx, dy) in directions {
-            let next_pos = Point {
-                x: pos.x + dx,
-                y: pos.y + dy,
-            };
-            if within_bounds(&next_pos, mem_size)
-                && !visited.contains(&next_pos)
-                && !corrupted.contains(&next_pos)
-            {
+        for next_pos in corrupted.neighbors4(pos) {
+            if !corrupted[next_pos] && !visited.contains(&next_pos) {
                 q.push_back((next_pos, steps + 1));
                 visited.insert(next_pos);
             }
@@ -62,34 +23,83 @@ fn min_steps(
     None
 }
 
-fn main() {
-    let bytes: Vec<_> = io::stdin()
-        .lines()
-        .map(|line| {
-            line.expect("error reading input")
-                .parse::<Point>()
-                .expect("error parsing point")
+pub struct Day18 {
+    bytes: Vec<Point>,
+    /// The largest coordinate of the memory space.
+    mem_size: isize,
+    /// Bytes fallen in part 1.
+    fallen: usize,
+}
+
+impl Day18 {
+    fn corrupted(&self, n: usize) -> Grid<bool> {
+        let size = self.mem_size as usize + 1;
+        let mut corrupted = Grid::new(size, size, false);
+        for &byte in self.bytes.iter().take(n) {
+            corrupted[byte] = true;
+        }
+        corrupted
+    }
+}
+
+impl Solution for Day18 {
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "size",
+            default: 70,
+            description: "largest coordinate of the memory space",
+        },
+        Param {
+            name: "bytes",
+            default: 1024,
+            description: "bytes fallen in part 1",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &Params::new(Self::PARAMS))
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let mem_size = params.get("size") as isize;
+        let bytes = input
+            .lines()
+            .map(|line| {
+                let byte = line.parse::<Point>().map_err(|e| e.within(input, line))?;
+                if byte.x < 0 || byte.x > mem_size || byte.y < 0 || byte.y > mem_size {
+                    return Err(ParseError::new(
+                        input,
+                        line,
+                        "a position within the memory space",
+                    ));
+                }
+                Ok(byte)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            bytes,
+            mem_size,
+            fallen: params.get("bytes") as usize,
         })
-        .collect();
+    }
 
-    let mem_size = 70;
-    let start = Point { x: 0, y: 0 };
-    let end = Point {
-        x: mem_size,
-        y: mem_size,
-    };
-    let mut corrupted: HashSet<_> = bytes.iter().take(1024).cloned().collect();
+    fn part1(&self) -> impl Answer {
+        let start = Point::new(0, 0);
+        let end = Point::new(self.mem_size, self.mem_size);
+        min_steps(start, end, &self.corrupted(self.fallen))
+    }
 
-    println!(
-        "part 1: {}",
-        min_steps(start, end, mem_size as usize, &corrupted).unwrap()
-    );
+    fn part2(&self) -> impl Answer {
+        let start = Point::new(0, 0);
+        let end = Point::new(self.mem_size, self.mem_size);
+        let mut corrupted = self.corrupted(self.fallen);
 
-    for i in 1025..bytes.len() {
-        corrupted.insert(bytes[i]);
-        if min_steps(start, end, mem_size as usize, &corrupted).is_none() {
-            println!("part 2: {},{}", bytes[i].x, bytes[i].y);
-            break;
+        for &byte in self.bytes.iter().skip(self.fallen) {
+            corrupted[byte] = true;
+            if min_steps(start, end, &corrupted).is_none() {
+                return Some(format!("{},{}", byte.x, byte.y));
+            }
         }
+        None
     }
 }
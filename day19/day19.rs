@@ -1,12 +1,12 @@
+use aoc::{Answer, ParseError, Solution};
 use std::collections::HashMap;
-use std::io;
 
 fn possible_arrangements<'a>(
     design: &'a str,
     available_patterns: &[&str],
     mem: &mut HashMap<&'a str, usize>,
 ) -> usize {
-    if design.len() == 0 {
+    if design.is_empty() {
         1
     } else if let Some(&n) = mem.get(design) {
         n
@@ -21,23 +21,41 @@ fn possible_arrangements<'a>(
     }
 }
 
-fn main() {
-    let input = io::read_to_string(io::stdin()).expect("error reading input");
-    let (available_patterns, designs) = input.split_once("\n\n").expect("error parsing input");
-    let available_patterns: Vec<_> = available_patterns.split(", ").collect();
-    let designs: Vec<_> = designs.lines().collect();
+pub struct Day19 {
+    available_patterns: Vec<String>,
+    designs: Vec<String>,
+}
 
-    let designs_arrangements: Vec<_> = designs
-        .iter()
-        .map(|design| possible_arrangements(design, &available_patterns, &mut HashMap::new()))
-        .collect();
+impl Day19 {
+    fn designs_arrangements(&self) -> impl Iterator<Item = usize> + '_ {
+        let available_patterns: Vec<_> =
+            self.available_patterns.iter().map(String::as_str).collect();
+        self.designs.iter().map(move |design| {
+            possible_arrangements(design, &available_patterns, &mut HashMap::new())
+        })
+    }
+}
 
-    println!(
-        "part 1: {}",
-        designs_arrangements
-            .iter()
-            .filter(|&&arrangements| arrangements > 0)
-            .count()
-    );
-    println!("part 2: {}", designs_arrangements.iter().sum::<usize>());
+impl Solution for Day19 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let (available_patterns, designs) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::end_of_input(input, "a blank line followed by the designs")
+        })?;
+        Ok(Self {
+            available_patterns: available_patterns.split(", ").map(String::from).collect(),
+            designs: designs.lines().map(String::from).collect(),
+        })
+    }
+
+    fn part1(&self) -> impl Answer {
+        Some(
+            self.designs_arrangements()
+                .filter(|&arrangements| arrangements > 0)
+                .count(),
+        )
+    }
+
+    fn part2(&self) -> impl Answer {
+        Some(self.designs_arrangements().sum::<usize>())
+    }
 }
@@ -1,42 +1,12 @@
+use aoc::{Answer, Grid, Param, Params, ParseError, Point, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::io;
-
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-impl Point {
-    fn distance(&self, other: &Point) -> usize {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
-    }
-}
 
 type Tile = char;
 
-fn find_tile(tile: Tile, map: &[Vec<Tile>]) -> Option<Point> {
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if map[y][x] == tile {
-                return Some(Point {
-                    x: x as isize,
-                    y: y as isize,
-                });
-            }
-        }
-    }
-    None
-}
-
-fn within_bounds(p: &Point, map: &[Vec<Tile>]) -> bool {
-    p.y >= 0 && (p.y as usize) < map.len() && p.x >= 0 && (p.x as usize) < map[p.y as usize].len()
-}
-
-fn bfs(start: &Point, end: &Point, map: &[Vec<Tile>], times: &mut HashMap<Point, usize>) {
-    if map[start.y as usize][start.x as usize] == '#' {
+fn bfs(start: &Point, end: &Point, map: &Grid<Tile>, times: &mut HashMap<Point, usize>) {
+    if map[*start] == '#' {
         return;
     }
 
@@ -52,16 +22,8 @@ fn bfs(start: &Point, end: &Point, map: &[Vec<Tile>], times: &mut HashMap<Point,
             continue;
         }
 
-        let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-        for (dx, dy) in directions {
-            let next_pos = Point {
-                x: pos.x + dx,
-                y: pos.y + dy,
-            };
-            if within_bounds(&next_pos, map)
-                && !visited.contains(&next_pos)
-                && map[next_pos.y as usize][next_pos.x as usize] != '#'
-            {
+        for next_pos in map.neighbors4(pos) {
+            if map[next_pos] != '#' && !visited.contains(&next_pos) {
                 q.push_back((next_pos, time + 1));
                 visited.insert(next_pos);
                 times.insert(next_pos, time + 1);
@@ -70,18 +32,23 @@ fn bfs(start: &Point, end: &Point, map: &[Vec<Tile>], times: &mut HashMap<Point,
     }
 }
 
-fn cheat_count(max_cheat_len: usize, end_time: usize, from_start_times: &HashMap<Point, usize>) -> usize {
+fn cheat_count(
+    max_cheat_len: usize,
+    min_saving: usize,
+    end_time: usize,
+    from_start_times: &HashMap<Point, usize>,
+) -> usize {
     let mut n = 0;
     for (cheat_start, t1) in from_start_times {
-        if t1 + 100 > end_time {
+        if t1 + min_saving > end_time {
             continue;
         }
         for (cheat_end, t) in from_start_times {
             let t2 = end_time - t;
-            let cheat_len = cheat_start.distance(&cheat_end);
+            let cheat_len = cheat_start.distance(cheat_end);
             if cheat_len <= max_cheat_len {
                 let time = t1 + cheat_len + t2;
-                if time + 100 <= end_time {
+                if time + min_saving <= end_time {
                     n += 1;
                 }
             }
@@ -90,19 +57,64 @@ fn cheat_count(max_cheat_len: usize, end_time: usize, from_start_times: &HashMap
     n
 }
 
-fn main() {
-    let map: Vec<Vec<_>> = io::stdin()
-        .lines()
-        .map(|line| line.expect("error reading input").chars().collect())
-        .collect();
+pub struct Day20 {
+    map: Grid<Tile>,
+    start: Point,
+    end: Point,
+    /// Picoseconds a cheat must save to be counted.
+    min_saving: usize,
+}
+
+impl Day20 {
+    fn cheat_count(&self, max_cheat_len: usize) -> Option<usize> {
+        let mut from_start_times = HashMap::new();
+        bfs(&self.start, &self.end, &self.map, &mut from_start_times);
+        let end_time = *from_start_times.get(&self.end)?;
+
+        Some(cheat_count(
+            max_cheat_len,
+            self.min_saving,
+            end_time,
+            &from_start_times,
+        ))
+    }
+}
 
-    let start = find_tile('S', &map).expect("start tile not found");
-    let end = find_tile('E', &map).expect("end tile not found");
+impl Solution for Day20 {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "min-saving",
+        default: 100,
+        description: "picoseconds a cheat must save to be counted",
+    }];
 
-    let mut from_start_times = HashMap::new();
-    bfs(&start, &end, &map, &mut from_start_times);
-    let end_time = *from_start_times.get(&end).unwrap();
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, &Params::new(Self::PARAMS))
+    }
 
-    println!("part 1: {}", cheat_count(2, end_time, &from_start_times));
-    println!("part 2: {}", cheat_count(20, end_time, &from_start_times));
+    fn parse_with(input: &str, params: &Params) -> Result<Self, ParseError> {
+        let map = Grid::parse(input, |c| match c {
+            '.' | '#' | 'S' | 'E' => Ok(c),
+            _ => Err("'.', '#', 'S' or 'E'"),
+        })?;
+        let start = map
+            .find(&'S')
+            .ok_or_else(|| ParseError::end_of_input(input, "a start tile 'S'"))?;
+        let end = map
+            .find(&'E')
+            .ok_or_else(|| ParseError::end_of_input(input, "an end tile 'E'"))?;
+        Ok(Self {
+            map,
+            start,
+            end,
+            min_saving: params.get("min-saving") as usize,
+        })
+    }
+
+    fn part1(&self) -> impl Answer {
+        self.cheat_count(2)
+    }
+
+    fn part2(&self) -> impl Answer {
+        self.cheat_count(20)
+    }
 }
@@ -0,0 +1,135 @@
+use aoc::{Answer, Grid, ParseError, Point, Solution};
+use std::collections::HashMap;
+
+/// Keypads are stored with a ' ' for the gap no robot arm may point at.
+fn keypad(rows: &[&str]) -> Grid<char> {
+    Grid::from_rows(rows.iter().map(|row| row.chars().collect()).collect())
+}
+
+fn key_position(keypad: &Grid<char>, key: char) -> Point {
+    keypad.find(&key).expect("key not on keypad")
+}
+
+/// The shortest direction key sequences moving a keypad's arm from `from` to
+/// `to`: all horizontal moves then all vertical ones, or the other way round,
+/// skipping a route that crosses the gap. Any other shortest route zigzags,
+/// which costs more presses further up the chain.
+fn routes(keypad: &Grid<char>, from: char, to: char) -> Vec<String> {
+    let from = key_position(keypad, from);
+    let to = key_position(keypad, to);
+    let delta = to - from;
+    let horizontal = if delta.x < 0 { "<" } else { ">" }.repeat(delta.x.unsigned_abs());
+    let vertical = if delta.y < 0 { "^" } else { "v" }.repeat(delta.y.unsigned_abs());
+
+    let mut routes = Vec::new();
+    if keypad[Point::new(to.x, from.y)] != ' ' {
+        routes.push(horizontal.clone() + &vertical + "A");
+    }
+    if keypad[Point::new(from.x, to.y)] != ' ' && !horizontal.is_empty() && !vertical.is_empty() {
+        routes.push(vertical + &horizontal + "A");
+    }
+    routes
+}
+
+struct Keypads {
+    numeric: Grid<char>,
+    directional: Grid<char>,
+    /// Presses for a directional keypad's arm to move between two keys and
+    /// press the second, by the number of keypads above it.
+    mem: HashMap<(char, char, usize), usize>,
+}
+
+impl Keypads {
+    fn new() -> Self {
+        Self {
+            numeric: keypad(&["789", "456", "123", " 0A"]),
+            directional: keypad(&[" ^A", "<v>"]),
+            mem: HashMap::new(),
+        }
+    }
+
+    /// Presses on our own keypad needed to type `keys` on a directional
+    /// keypad with `depth` directional keypads above it, every arm starting
+    /// on 'A'.
+    fn sequence_presses(&mut self, keys: &str, depth: usize) -> usize {
+        if depth == 0 {
+            return keys.len();
+        }
+        let mut from = 'A';
+        let mut presses = 0;
+        for to in keys.chars() {
+            presses += self.key_presses(from, to, depth);
+            from = to;
+        }
+        presses
+    }
+
+    fn key_presses(&mut self, from: char, to: char, depth: usize) -> usize {
+        if let Some(&presses) = self.mem.get(&(from, to, depth)) {
+            return presses;
+        }
+        let presses = routes(&self.directional, from, to)
+            .iter()
+            .map(|route| self.sequence_presses(route, depth - 1))
+            .min()
+            .unwrap();
+        self.mem.insert((from, to, depth), presses);
+        presses
+    }
+
+    /// Presses needed to type `code` on the numeric keypad through `robots`
+    /// robot-operated directional keypads and the one we use.
+    fn code_presses(&mut self, code: &str, robots: usize) -> usize {
+        let mut from = 'A';
+        let mut presses = 0;
+        for to in code.chars() {
+            presses += routes(&self.numeric, from, to)
+                .iter()
+                .map(|route| self.sequence_presses(route, robots))
+                .min()
+                .unwrap();
+            from = to;
+        }
+        presses
+    }
+}
+
+pub struct Day21 {
+    /// Each code with its numeric part.
+    codes: Vec<(String, usize)>,
+}
+
+impl Day21 {
+    fn complexity_sum(&self, robots: usize) -> usize {
+        let mut keypads = Keypads::new();
+        self.codes
+            .iter()
+            .map(|(code, value)| keypads.code_presses(code, robots) * value)
+            .sum()
+    }
+}
+
+impl Solution for Day21 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let codes = input
+            .lines()
+            .map(|line| {
+                let digits = line
+                    .strip_suffix('A')
+                    .filter(|digits| !digits.is_empty())
+                    .filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))
+                    .ok_or_else(|| ParseError::new(input, line, "a door code like '029A'"))?;
+                Ok((line.to_string(), aoc::parse(input, digits, "a number")?))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { codes })
+    }
+
+    fn part1(&self) -> impl Answer {
+        Some(self.complexity_sum(2))
+    }
+
+    fn part2(&self) -> impl Answer {
+        Some(self.complexity_sum(25))
+    }
+}
@@ -0,0 +1,66 @@
+use aoc::{parse, Answer, ParseError, Solution};
+
+type Secret = u64;
+
+const STEPS: usize = 2000;
+
+fn next_secret(secret: Secret) -> Secret {
+    const PRUNE: Secret = 16777216;
+    let secret = (secret ^ (secret * 64)) % PRUNE;
+    let secret = (secret ^ (secret / 32)) % PRUNE;
+    (secret ^ (secret * 2048)) % PRUNE
+}
+
+fn secrets(secret: Secret) -> impl Iterator<Item = Secret> {
+    std::iter::successors(Some(secret), |&secret| Some(next_secret(secret)))
+}
+
+/// Packs the last four price changes, each in -9..=9, into one index.
+fn push_change(sequence: usize, change: i64) -> usize {
+    (sequence * 19 + (change + 9) as usize) % (19 * 19 * 19 * 19)
+}
+
+pub struct Day22 {
+    secrets: Vec<Secret>,
+}
+
+impl Solution for Day22 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let secrets = input
+            .lines()
+            .map(|line| parse(input, line, "a secret number"))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { secrets })
+    }
+
+    fn part1(&self) -> impl Answer {
+        Some(
+            self.secrets
+                .iter()
+                .map(|&secret| secrets(secret).nth(STEPS).unwrap())
+                .sum::<Secret>(),
+        )
+    }
+
+    fn part2(&self) -> impl Answer {
+        // bananas bought with each sequence of four changes, over all buyers;
+        // a buyer sells at the first occurrence of the sequence only
+        let mut bananas = vec![0; 19 * 19 * 19 * 19];
+        let mut seen_by = vec![usize::MAX; bananas.len()];
+        for (buyer, &secret) in self.secrets.iter().enumerate() {
+            let prices: Vec<_> = secrets(secret)
+                .take(STEPS + 1)
+                .map(|secret| (secret % 10) as i64)
+                .collect();
+            let mut sequence = 0;
+            for (i, window) in prices.windows(2).enumerate() {
+                sequence = push_change(sequence, window[1] - window[0]);
+                if i >= 3 && seen_by[sequence] != buyer {
+                    seen_by[sequence] = buyer;
+                    bananas[sequence] += window[1];
+                }
+            }
+        }
+        bananas.into_iter().max()
+    }
+}
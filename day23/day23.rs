@@ -0,0 +1,95 @@
+use aoc::{Answer, ParseError, Solution};
+use std::collections::{BTreeSet, HashMap};
+
+type Computer = usize;
+
+/// Extends the clique `r` with computers from `p` and none from `x`
+/// (Bron–Kerbosch with pivoting), keeping the largest maximal clique found.
+fn largest_clique(
+    r: &mut Vec<Computer>,
+    mut p: BTreeSet<Computer>,
+    mut x: BTreeSet<Computer>,
+    connections: &[BTreeSet<Computer>],
+    largest: &mut Vec<Computer>,
+) {
+    let Some(&pivot) = p.union(&x).max_by_key(|&&c| connections[c].len()) else {
+        if r.len() > largest.len() {
+            largest.clone_from(r);
+        }
+        return;
+    };
+    let candidates: Vec<_> = p.difference(&connections[pivot]).copied().collect();
+    for c in candidates {
+        r.push(c);
+        largest_clique(
+            r,
+            p.intersection(&connections[c]).copied().collect(),
+            x.intersection(&connections[c]).copied().collect(),
+            connections,
+            largest,
+        );
+        r.pop();
+        p.remove(&c);
+        x.insert(c);
+    }
+}
+
+pub struct Day23 {
+    names: Vec<String>,
+    connections: Vec<BTreeSet<Computer>>,
+}
+
+impl Solution for Day23 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut ids = HashMap::new();
+        let mut names = Vec::new();
+        let mut connections: Vec<BTreeSet<Computer>> = Vec::new();
+        for line in input.lines() {
+            let (a, b) = line
+                .split_once('-')
+                .filter(|(a, b)| !a.is_empty() && !b.is_empty() && a != b)
+                .ok_or_else(|| ParseError::new(input, line, "a connection 'a-b'"))?;
+            let [a, b] = [a, b].map(|name| {
+                *ids.entry(name).or_insert_with(|| {
+                    names.push(name.to_string());
+                    connections.push(BTreeSet::new());
+                    names.len() - 1
+                })
+            });
+            connections[a].insert(b);
+            connections[b].insert(a);
+        }
+        Ok(Self { names, connections })
+    }
+
+    fn part1(&self) -> impl Answer {
+        // count each triangle once, from its lowest computer
+        let mut n = 0;
+        for (a, connections) in self.connections.iter().enumerate() {
+            for &b in connections.range(a + 1..) {
+                for &c in self.connections[b].range(b + 1..) {
+                    if connections.contains(&c)
+                        && [a, b, c].iter().any(|&i| self.names[i].starts_with('t'))
+                    {
+                        n += 1;
+                    }
+                }
+            }
+        }
+        Some(n)
+    }
+
+    fn part2(&self) -> impl Answer {
+        let mut largest = Vec::new();
+        largest_clique(
+            &mut Vec::new(),
+            (0..self.names.len()).collect(),
+            BTreeSet::new(),
+            &self.connections,
+            &mut largest,
+        );
+        let mut names: Vec<_> = largest.iter().map(|&c| self.names[c].as_str()).collect();
+        names.sort();
+        Some(names.join(","))
+    }
+}
@@ -0,0 +1,158 @@
+use aoc::{Answer, ParseError, Solution};
+use std::collections::HashMap;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Clone, Debug)]
+struct Gate {
+    inputs: [String; 2],
+    op: Op,
+    output: String,
+}
+
+impl Gate {
+    fn has_input_wires(&self) -> bool {
+        self.inputs
+            .iter()
+            .all(|wire| wire.starts_with('x') || wire.starts_with('y'))
+    }
+
+    fn is_first_bit(&self) -> bool {
+        self.inputs.iter().all(|wire| &wire[1..] == "00")
+    }
+}
+
+fn parse_gate(input: &str, line: &str) -> Result<Gate, ParseError> {
+    let fields: Vec<_> = line.split_whitespace().collect();
+    let error = || ParseError::new(input, line, "a gate 'a OP b -> c'");
+    let [a, op, b, "->", output] = fields[..] else {
+        return Err(error());
+    };
+    let op = match op {
+        "AND" => Op::And,
+        "OR" => Op::Or,
+        "XOR" => Op::Xor,
+        _ => return Err(ParseError::new(input, op, "'AND', 'OR' or 'XOR'")),
+    };
+    Ok(Gate {
+        inputs: [a.to_string(), b.to_string()],
+        op,
+        output: output.to_string(),
+    })
+}
+
+/// The value of `wire`, or `None` if it has no value or depends on itself.
+fn value<'a>(
+    wire: &'a str,
+    gates: &'a HashMap<String, Gate>,
+    values: &mut HashMap<&'a str, Option<bool>>,
+) -> Option<bool> {
+    if let Some(&value) = values.get(wire) {
+        return value;
+    }
+    // a wire being computed has no value yet, which breaks loops
+    values.insert(wire, None);
+    let gate = gates.get(wire)?;
+    let a = value(&gate.inputs[0], gates, values)?;
+    let b = value(&gate.inputs[1], gates, values)?;
+    let value = match gate.op {
+        Op::And => a & b,
+        Op::Or => a | b,
+        Op::Xor => a ^ b,
+    };
+    values.insert(wire, Some(value));
+    Some(value)
+}
+
+pub struct Day24 {
+    initial_values: Vec<(String, bool)>,
+    /// Gates by their output wire.
+    gates: HashMap<String, Gate>,
+}
+
+impl Solution for Day24 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let (wires, gates) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::end_of_input(input, "a blank line followed by the gates"))?;
+        let initial_values = wires
+            .lines()
+            .map(|line| match line.split_once(": ") {
+                Some((wire, "0")) => Ok((wire.to_string(), false)),
+                Some((wire, "1")) => Ok((wire.to_string(), true)),
+                _ => Err(ParseError::new(input, line, "a wire value 'name: 0|1'")),
+            })
+            .collect::<Result<_, _>>()?;
+        let gates = gates
+            .lines()
+            .map(|line| parse_gate(input, line).map(|gate| (gate.output.clone(), gate)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            initial_values,
+            gates,
+        })
+    }
+
+    fn part1(&self) -> impl Answer {
+        let mut values: HashMap<&str, Option<bool>> = self
+            .initial_values
+            .iter()
+            .map(|(wire, value)| (wire.as_str(), Some(*value)))
+            .collect();
+        let mut z_wires: Vec<_> = self.gates.keys().filter(|w| w.starts_with('z')).collect();
+        z_wires.sort();
+        let mut number = 0_u64;
+        for wire in z_wires.iter().rev() {
+            number = number << 1 | value(wire, &self.gates, &mut values)? as u64;
+        }
+        Some(number)
+    }
+
+    fn part2(&self) -> impl Answer {
+        // The gates should form a ripple-carry adder: for each bit i > 0,
+        //   xi XOR yi -> s, s XOR carry -> zi,
+        //   xi AND yi -> c1, s AND carry -> c2, c1 OR c2 -> next carry,
+        // with a half adder for bit 0 and the last carry as the top z bit.
+        // A gate whose output doesn't fit this wiring has been swapped.
+        let bits = self
+            .initial_values
+            .iter()
+            .filter(|(wire, _)| wire.starts_with('x'))
+            .count();
+        if bits == 0 || self.gates.len() != 5 * bits - 3 {
+            return None;
+        }
+        let last_z = format!("z{:02}", bits);
+        let feeds = |wire: &str, op: Op| {
+            self.gates
+                .values()
+                .any(|gate| gate.op == op && gate.inputs.iter().any(|input| input == wire))
+        };
+
+        let mut swapped: Vec<_> = self
+            .gates
+            .values()
+            .filter(|gate| {
+                let to_z = gate.output.starts_with('z');
+                match gate.op {
+                    _ if to_z && gate.output != last_z && gate.op != Op::Xor => true,
+                    Op::Xor if !gate.has_input_wires() => !to_z,
+                    Op::Xor if !gate.is_first_bit() => !feeds(&gate.output, Op::Xor),
+                    Op::And if !gate.is_first_bit() => !feeds(&gate.output, Op::Or),
+                    _ => false,
+                }
+            })
+            .map(|gate| gate.output.as_str())
+            .collect();
+        if swapped.len() != 8 {
+            return None;
+        }
+        swapped.sort();
+        Some(swapped.join(","))
+    }
+}
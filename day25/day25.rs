@@ -0,0 +1,74 @@
+use aoc::{Answer, Grid, ParseError, Solution};
+
+/// The height of each column of a lock's pins or a key's cuts.
+type Heights = Vec<usize>;
+
+fn first_line(schematic: &str) -> &str {
+    schematic.lines().next().unwrap_or(schematic)
+}
+
+pub struct Day25 {
+    locks: Vec<Heights>,
+    keys: Vec<Heights>,
+    /// The number of rows a pin and a cut may share between them.
+    space: usize,
+}
+
+impl Solution for Day25 {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut locks = Vec::new();
+        let mut keys = Vec::new();
+        let mut size = None;
+        for schematic in input.split("\n\n") {
+            let grid = Grid::parse(schematic, |c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err("'#' or '.'"),
+            })
+            .map_err(|e| e.within(input, schematic))?;
+            if *size.get_or_insert((grid.width(), grid.height())) != (grid.width(), grid.height()) {
+                return Err(ParseError::new(
+                    input,
+                    first_line(schematic),
+                    "a schematic the size of the first one",
+                ));
+            }
+
+            let filled = |row: Option<&[bool]>| row.is_some_and(|row| row.iter().all(|&f| f));
+            let schematics = if filled(grid.rows().next()) {
+                &mut locks
+            } else if filled(grid.rows().last()) {
+                &mut keys
+            } else {
+                return Err(ParseError::new(
+                    input,
+                    first_line(schematic),
+                    "a lock with a filled top row or a key with a filled bottom row",
+                ));
+            };
+            // the filled top or bottom row isn't part of the height
+            schematics.push(
+                grid.columns()
+                    .map(|column| column.filter(|&&f| f).count() - 1)
+                    .collect(),
+            );
+        }
+        let space = size.map_or(0, |(_, height)| height.saturating_sub(2));
+        Ok(Self { locks, keys, space })
+    }
+
+    fn part1(&self) -> impl Answer {
+        Some(
+            self.locks
+                .iter()
+                .flat_map(|lock| self.keys.iter().map(move |key| (lock, key)))
+                .filter(|(lock, key)| lock.iter().zip(*key).all(|(l, k)| l + k <= self.space))
+                .count(),
+        )
+    }
+
+    fn part2(&self) -> impl Answer {
+        // the last day has no second puzzle
+        None::<usize>
+    }
+}
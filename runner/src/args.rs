@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Command line arguments of a subcommand: positional values followed by
+/// `--name value` flags. Every flag must be consumed by the subcommand, so
+/// misspelled flags are reported instead of ignored.
+pub struct Args {
+    positional: Vec<String>,
+    flags: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut flags = HashMap::new();
+        while let Some(arg) = args.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let value = args.next().ok_or(format!("missing value for {}", arg))?;
+                if flags.insert(name.to_string(), value).is_some() {
+                    return Err(format!("duplicate argument: {}", arg));
+                }
+            } else {
+                positional.push(arg);
+            }
+        }
+        Ok(Self { positional, flags })
+    }
+
+    pub fn positional<T: FromStr>(&self, index: usize, name: &str) -> Result<T, String> {
+        let value = self
+            .positional
+            .get(index)
+            .ok_or(format!("missing {}", name))?;
+        value
+            .parse()
+            .map_err(|_| format!("invalid {}: {}", name, value))
+    }
+
+    pub fn flag<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        self.flags
+            .remove(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid value for --{}: {}", name, value))
+            })
+            .transpose()
+    }
+
+    /// Consumes every remaining flag, in name order.
+    pub fn take_flags(&mut self) -> Vec<(String, String)> {
+        let mut flags: Vec<_> = self.flags.drain().collect();
+        flags.sort();
+        flags
+    }
+
+    /// Fails if the arguments contain more than `positional` values or flags
+    /// that weren't consumed.
+    pub fn finish(self, positional: usize) -> Result<(), String> {
+        if let Some(arg) = self.positional.get(positional) {
+            return Err(format!("unexpected argument: {}", arg));
+        }
+        if let Some(name) = self.flags.keys().min() {
+            return Err(format!("unexpected argument: --{}", name));
+        }
+        Ok(())
+    }
+}
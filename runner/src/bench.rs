@@ -0,0 +1,172 @@
+//! Repeated timing of a day's phases, and comparison of saved results so
+//! that two runs can be checked for regressions.
+
+use crate::Day;
+use aoc::{Params, Part, SolveError};
+use std::fmt::Write;
+use std::time::Duration;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let n = samples.len();
+        let median = match n {
+            0 => return None,
+            _ if n.is_multiple_of(2) => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+            _ => samples[n / 2],
+        };
+        Some(Self {
+            min: samples[0],
+            median,
+            max: samples[n - 1],
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Measurement {
+    pub day: u8,
+    /// `parse`, `part1` or `part2`.
+    pub phase: String,
+    pub iterations: usize,
+    pub stats: Stats,
+}
+
+/// Solves `input` `iterations` times and gathers the statistics of every
+/// phase that was run.
+pub fn bench(
+    day: &Day,
+    input: &str,
+    params: &Params,
+    part: Option<Part>,
+    iterations: usize,
+) -> Result<Vec<Measurement>, SolveError> {
+    let mut parse = Vec::new();
+    let mut part1 = Vec::new();
+    let mut part2 = Vec::new();
+    for _ in 0..iterations {
+        let timings = (day.solve)(input, params, part)?.timings;
+        parse.push(timings.parse);
+        part1.extend(timings.part1);
+        part2.extend(timings.part2);
+    }
+
+    Ok([("parse", parse), ("part1", part1), ("part2", part2)]
+        .into_iter()
+        .filter_map(|(phase, samples)| {
+            Some(Measurement {
+                day: day.number,
+                phase: phase.to_string(),
+                iterations: samples.len(),
+                stats: Stats::new(&samples)?,
+            })
+        })
+        .collect())
+}
+
+/// Serializes measurements as tab separated lines, times in nanoseconds.
+pub fn format_results(measurements: &[Measurement]) -> String {
+    let mut s = String::from("# day\tphase\titerations\tmin_ns\tmedian_ns\tmax_ns\n");
+    for m in measurements {
+        writeln!(
+            s,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            m.day,
+            m.phase,
+            m.iterations,
+            m.stats.min.as_nanos(),
+            m.stats.median.as_nanos(),
+            m.stats.max.as_nanos()
+        )
+        .unwrap();
+    }
+    s
+}
+
+pub fn parse_results(s: &str) -> Result<Vec<Measurement>, String> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            let invalid = || format!("line {}: invalid bench result: {}", i + 1, line);
+            let fields: Vec<_> = line.split('\t').collect();
+            let [day, phase, iterations, min, median, max] = fields[..] else {
+                return Err(invalid());
+            };
+            let nanos = |s: &str| s.parse().map(Duration::from_nanos).map_err(|_| invalid());
+            Ok(Measurement {
+                day: day.parse().map_err(|_| invalid())?,
+                phase: phase.to_string(),
+                iterations: iterations.parse().map_err(|_| invalid())?,
+                stats: Stats {
+                    min: nanos(min)?,
+                    median: nanos(median)?,
+                    max: nanos(max)?,
+                },
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub day: u8,
+    pub phase: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// Relative change of the median time, in percent.
+    pub fn change(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+/// Pairs up the medians of the phases measured in both runs.
+pub fn compare(baseline: &[Measurement], current: &[Measurement]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|m| {
+            let b = baseline
+                .iter()
+                .find(|b| b.day == m.day && b.phase == m.phase)?;
+            Some(Comparison {
+                day: m.day,
+                phase: m.phase.clone(),
+                baseline: b.stats.median,
+                current: m.stats.median,
+            })
+        })
+        .collect()
+}
+
+/// Formats a duration with three significant digits, e.g. `12.3ms`.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos() as f64;
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, "ns")
+    } else if nanos < 1e6 {
+        (nanos / 1e3, "µs")
+    } else if nanos < 1e9 {
+        (nanos / 1e6, "ms")
+    } else {
+        (nanos / 1e9, "s")
+    };
+    let precision = if value < 10.0 {
+        2
+    } else if value < 100.0 {
+        1
+    } else {
+        0
+    };
+    format!("{:.*}{}", precision, value, unit)
+}
@@ -0,0 +1,57 @@
+//! JSON rendering of a day's answers, for tools that consume `aoc run`.
+
+use aoc::Answers;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Writes `s` as a JSON string literal.
+fn string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Integer answers become JSON numbers, anything else a string, and parts
+/// without an answer `null`. Only answers written the way JSON writes the
+/// number are numbers, so `007` or `+5` stay strings.
+fn answer(answer: Option<&str>) -> String {
+    let integer = |a: &str| {
+        a.parse::<i64>().is_ok_and(|n| a == n.to_string())
+            || a.parse::<u64>().is_ok_and(|n| a == n.to_string())
+    };
+    match answer {
+        Some(a) if integer(a) => a.to_string(),
+        Some(a) => string(a),
+        None => String::from("null"),
+    }
+}
+
+fn nanos(time: Option<Duration>) -> String {
+    time.map_or(String::from("null"), |t| t.as_nanos().to_string())
+}
+
+/// Renders the answers as a single-line object, with timings in nanoseconds:
+/// `{"day":14,"part1":1,"part2":2,"timings":{"parse_ns":..,"part1_ns":..,"part2_ns":..}}`.
+pub fn answers(day: u8, answers: &Answers) -> String {
+    let timings = &answers.timings;
+    format!(
+        "{{\"day\":{},\"part1\":{},\"part2\":{},\"timings\":{{\"parse_ns\":{},\"part1_ns\":{},\"part2_ns\":{}}}}}",
+        day,
+        answer(answers.part1.as_deref()),
+        answer(answers.part2.as_deref()),
+        timings.parse.as_nanos(),
+        nanos(timings.part1),
+        nanos(timings.part2)
+    )
+}
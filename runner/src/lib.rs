@@ -0,0 +1,57 @@
+pub mod bench;
+pub mod json;
+
+use aoc::{solve, Answers, Param, Params, Part, Solution, SolveError};
+
+pub struct Day {
+    pub number: u8,
+    pub params: &'static [Param],
+    pub solve: fn(&str, &Params, Option<Part>) -> Result<Answers, SolveError>,
+}
+
+impl Day {
+    const fn new<S: Solution>(number: u8) -> Self {
+        Self {
+            number,
+            params: S::PARAMS,
+            solve: solve::<S>,
+        }
+    }
+
+    /// The puzzle values of the day's parameters.
+    pub fn default_params(&self) -> Params {
+        Params::new(self.params)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(1),
+    Day::new::<day02::Day02>(2),
+    Day::new::<day03::Day03>(3),
+    Day::new::<day04::Day04>(4),
+    Day::new::<day05::Day05>(5),
+    Day::new::<day06::Day06>(6),
+    Day::new::<day07::Day07>(7),
+    Day::new::<day08::Day08>(8),
+    Day::new::<day09::Day09>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+    Day::new::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+    Day::new::<day20::Day20>(20),
+    Day::new::<day21::Day21>(21),
+    Day::new::<day22::Day22>(22),
+    Day::new::<day23::Day23>(23),
+    Day::new::<day24::Day24>(24),
+    Day::new::<day25::Day25>(25),
+];
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,252 @@
+mod args;
+
+use aoc::{Params, ParseError, Part, SolveError};
+use args::Args;
+use runner::bench::{self, format_duration};
+use runner::json;
+use runner::Day;
+use std::env;
+use std::fs;
+use std::io;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: aoc run <day> [--part <1|2>] [--input <file>] [--format <text|json>] [--<param> <n>...]
+       aoc bench <day> [--part <1|2>] [--input <file>] [--iterations <n>] [--output <file>]
+                 [--<param> <n>...]
+       aoc params <day>
+       aoc compare <baseline> <results> [--threshold <percent>]";
+
+enum Error {
+    Usage(String),
+    Failure(String),
+}
+
+impl From<String> for Error {
+    fn from(e: String) -> Self {
+        Error::Failure(e)
+    }
+}
+
+fn read_input(path: Option<&str>) -> Result<String, String> {
+    match path {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e)),
+        None => io::read_to_string(io::stdin()).map_err(|e| format!("error reading input: {}", e)),
+    }
+}
+
+/// Formats a parse error followed by the offending input line, with the
+/// offending text underlined.
+fn format_parse_error(e: &ParseError, input: &str) -> String {
+    let mut message = format!("invalid input: {}", e);
+    if let Some(line) = input.lines().nth(e.line - 1) {
+        let number = e.line.to_string();
+        let margin = " ".repeat(number.len());
+        message += &format!(
+            "\n {} | {}\n {} | {}{}",
+            number,
+            line,
+            margin,
+            " ".repeat(e.column - 1),
+            "^".repeat(e.text.lines().next().unwrap_or("").chars().count().max(1))
+        );
+    }
+    message
+}
+
+/// Formats why the input couldn't be solved, parse errors with their line.
+fn format_solve_error(e: &SolveError, input: &str) -> String {
+    match e {
+        SolveError::Parse(e) => format_parse_error(e, input),
+        SolveError::Part(..) => e.to_string(),
+    }
+}
+
+/// The day, part and input file shared by `run` and `bench`.
+fn puzzle_args(args: &mut Args) -> Result<(&'static Day, Option<Part>, Option<String>), Error> {
+    let number: u8 = args.positional(0, "day").map_err(Error::Usage)?;
+    let part = match args.flag::<u8>("part").map_err(Error::Usage)? {
+        None => None,
+        Some(1) => Some(Part::One),
+        Some(2) => Some(Part::Two),
+        Some(part) => return Err(Error::Usage(format!("invalid part: {}", part))),
+    };
+    let input = args.flag("input").map_err(Error::Usage)?;
+
+    let day = runner::find_day(number).ok_or(format!("no solution for day {}", number))?;
+    Ok((day, part, input))
+}
+
+/// The day's parameters, set from the flags left in `args`. Must come after
+/// every other flag is read.
+fn puzzle_params(day: &Day, args: &mut Args) -> Result<Params, Error> {
+    let mut params = day.default_params();
+    for (name, value) in args.take_flags() {
+        let value = value
+            .parse()
+            .map_err(|_| Error::Usage(format!("invalid value for --{}: {}", name, value)))?;
+        params
+            .set(&name, value)
+            .map_err(|_| Error::Usage(format!("unexpected argument: --{}", name)))?;
+    }
+    Ok(params)
+}
+
+fn run(mut args: Args) -> Result<(), Error> {
+    let (day, part, input) = puzzle_args(&mut args)?;
+    let format: Option<String> = args.flag("format").map_err(Error::Usage)?;
+    let params = puzzle_params(day, &mut args)?;
+    args.finish(1).map_err(Error::Usage)?;
+    let json = match format.as_deref() {
+        None | Some("text") => false,
+        Some("json") => true,
+        Some(format) => return Err(Error::Usage(format!("invalid format: {}", format))),
+    };
+    let input = read_input(input.as_deref())?;
+
+    let answers = (day.solve)(&input, &params, part).map_err(|e| format_solve_error(&e, &input))?;
+    // diagnostics go to stderr, keeping stdout for the answers
+    if let Some(diagnostics) = &answers.diagnostics {
+        eprintln!("{}", diagnostics);
+    }
+    if json {
+        println!("{}", json::answers(day.number, &answers));
+        return Ok(());
+    }
+    if let Some(answer) = answers.part1 {
+        println!("part 1: {}", answer);
+    }
+    if let Some(answer) = answers.part2 {
+        println!("part 2: {}", answer);
+    }
+    Ok(())
+}
+
+fn bench(mut args: Args) -> Result<(), Error> {
+    let (day, part, input) = puzzle_args(&mut args)?;
+    let iterations = args.flag("iterations").map_err(Error::Usage)?.unwrap_or(10);
+    let output: Option<String> = args.flag("output").map_err(Error::Usage)?;
+    let params = puzzle_params(day, &mut args)?;
+    args.finish(1).map_err(Error::Usage)?;
+    if iterations == 0 {
+        return Err(Error::Usage(String::from(
+            "invalid value for --iterations: 0",
+        )));
+    }
+    let input = read_input(input.as_deref())?;
+
+    let measurements = bench::bench(day, &input, &params, part, iterations)
+        .map_err(|e| format_solve_error(&e, &input))?;
+
+    println!("day {}, {} iterations", day.number, iterations);
+    println!(
+        "{:<6} {:>10} {:>10} {:>10}",
+        "phase", "min", "median", "max"
+    );
+    for m in &measurements {
+        println!(
+            "{:<6} {:>10} {:>10} {:>10}",
+            m.phase,
+            format_duration(m.stats.min),
+            format_duration(m.stats.median),
+            format_duration(m.stats.max)
+        );
+    }
+
+    if let Some(path) = output {
+        fs::write(&path, bench::format_results(&measurements))
+            .map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+fn params(args: Args) -> Result<(), Error> {
+    let number: u8 = args.positional(0, "day").map_err(Error::Usage)?;
+    args.finish(1).map_err(Error::Usage)?;
+
+    let day = runner::find_day(number).ok_or(format!("no solution for day {}", number))?;
+    for param in day.params {
+        println!(
+            "--{:<16} {:<16} {}",
+            param.name, param.default, param.description
+        );
+    }
+    Ok(())
+}
+
+fn compare(mut args: Args) -> Result<(), Error> {
+    let baseline: String = args.positional(0, "baseline").map_err(Error::Usage)?;
+    let results: String = args.positional(1, "results").map_err(Error::Usage)?;
+    let threshold: f64 = args
+        .flag("threshold")
+        .map_err(Error::Usage)?
+        .unwrap_or(10.0);
+    args.finish(2).map_err(Error::Usage)?;
+
+    let read = |path: &str| {
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| bench::parse_results(&s))
+            .map_err(|e| format!("{}: {}", path, e))
+    };
+    let comparisons = bench::compare(&read(&baseline)?, &read(&results)?);
+
+    let mut regressions = 0;
+    println!(
+        "{:<4} {:<6} {:>10} {:>10} {:>8}",
+        "day", "phase", "baseline", "median", "change"
+    );
+    for c in &comparisons {
+        let regression = c.change() > threshold;
+        if regression {
+            regressions += 1;
+        }
+        println!(
+            "{:<4} {:<6} {:>10} {:>10} {:>+7.1}%{}",
+            c.day,
+            c.phase,
+            format_duration(c.baseline),
+            format_duration(c.current),
+            c.change(),
+            if regression { "  regression" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        Err(Error::Failure(format!(
+            "{} phase(s) slower by more than {}%",
+            regressions, threshold
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let result =
+        Args::parse(args)
+            .map_err(Error::Usage)
+            .and_then(|args| match command.as_deref() {
+                Some("run") => run(args),
+                Some("bench") => bench(args),
+                Some("params") => params(args),
+                Some("compare") => compare(args),
+                Some(command) => Err(Error::Usage(format!("unknown command: {}", command))),
+                None => Err(Error::Usage(String::from("missing command"))),
+            });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Error::Usage(e)) => {
+            eprintln!("error: {}", e);
+            eprintln!("{}", USAGE);
+            ExitCode::from(2)
+        }
+        Err(Error::Failure(e)) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
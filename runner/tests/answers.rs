@@ -0,0 +1,129 @@
+//! Checks every day against the answers recorded in its `answers` file.
+//!
+//! Each non-comment line of `dayNN/answers` names an input file of that day
+//! followed by the expected part 1 and part 2 answers, `-` meaning the part
+//! isn't checked for that input, and optionally by `name=value` parameters
+//! to solve it with.
+
+use aoc::Part;
+use std::fs;
+use std::path::PathBuf;
+
+fn check_answers(day: u8) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{:02}", day));
+    let answers = fs::read_to_string(dir.join("answers")).expect("error reading answers");
+    let day_solution = runner::find_day(day).expect("day not registered");
+
+    for line in answers
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        let fields: Vec<_> = line.split_whitespace().collect();
+        let [file, part1, part2, ref params @ ..] = fields[..] else {
+            panic!("day {}: invalid answers line: {}", day, line);
+        };
+        let input = fs::read_to_string(dir.join(file)).expect("error reading input");
+        let mut day_params = day_solution.default_params();
+        for param in params {
+            let (name, value) = param
+                .split_once('=')
+                .and_then(|(name, value)| Some((name, value.parse().ok()?)))
+                .unwrap_or_else(|| panic!("day {}: invalid parameter: {}", day, param));
+            day_params
+                .set(name, value)
+                .unwrap_or_else(|e| panic!("day {}: {}", day, e));
+        }
+
+        let part = match (part1, part2) {
+            (_, "-") => Some(Part::One),
+            ("-", _) => Some(Part::Two),
+            _ => None,
+        };
+        let answers = (day_solution.solve)(&input, &day_params, part)
+            .unwrap_or_else(|e| panic!("day {} {}: {}", day, file, e));
+        if part1 != "-" {
+            assert_eq!(
+                answers.part1.as_deref(),
+                Some(part1),
+                "day {} {} part 1",
+                day,
+                file
+            );
+        }
+        if part2 != "-" {
+            assert_eq!(
+                answers.part2.as_deref(),
+                Some(part2),
+                "day {} {} part 2",
+                day,
+                file
+            );
+        }
+    }
+}
+
+macro_rules! answers_tests {
+    ($($name:ident: $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_answers($day);
+            }
+        )*
+    };
+}
+
+answers_tests! {
+    day01: 1,
+    day02: 2,
+    day03: 3,
+    day04: 4,
+    day05: 5,
+    day06: 6,
+    day07: 7,
+    day08: 8,
+    day09: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+    day18: 18,
+    day19: 19,
+    day20: 20,
+    day21: 21,
+    day22: 22,
+    day23: 23,
+    day24: 24,
+    day25: 25,
+}
+
+#[test]
+fn every_day_has_answers() {
+    for day in runner::DAYS {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(format!("day{:02}/answers", day.number));
+        assert!(path.exists(), "missing {}", path.display());
+    }
+}
+
+#[test]
+fn day14_picture() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../day14");
+    let input = fs::read_to_string(dir.join("input")).expect("error reading input");
+    let day = runner::find_day(14).unwrap();
+
+    let answers = (day.solve)(&input, &day.default_params(), None).unwrap();
+    let picture = answers.diagnostics.expect("part 2 should draw the tree");
+    assert_eq!(picture.lines().count(), 103);
+    assert!(picture.contains(&"#".repeat(11)));
+
+    let answers = (day.solve)(&input, &day.default_params(), Some(Part::One)).unwrap();
+    assert!(answers.diagnostics.is_none());
+}
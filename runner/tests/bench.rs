@@ -0,0 +1,57 @@
+use runner::bench::{self, Measurement, Stats};
+use std::time::Duration;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+fn measurement(phase: &str, median: u64) -> Measurement {
+    Measurement {
+        day: 16,
+        phase: phase.to_string(),
+        iterations: 3,
+        stats: Stats {
+            min: ms(median - 1),
+            median: ms(median),
+            max: ms(median + 1),
+        },
+    }
+}
+
+#[test]
+fn stats() {
+    let stats = Stats::new(&[ms(5), ms(1), ms(3)]).unwrap();
+    assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(5)));
+
+    let stats = Stats::new(&[ms(4), ms(1), ms(2), ms(8)]).unwrap();
+    assert_eq!(stats.median, ms(3));
+
+    assert!(Stats::new(&[]).is_none());
+}
+
+#[test]
+fn results_round_trip() {
+    let measurements = vec![measurement("parse", 2), measurement("part1", 30)];
+    let s = bench::format_results(&measurements);
+    assert_eq!(bench::parse_results(&s).unwrap(), measurements);
+    assert!(bench::parse_results("16\tparse\t3\n").is_err());
+}
+
+#[test]
+fn compare_medians() {
+    let baseline = [measurement("parse", 2), measurement("part1", 40)];
+    let current = [measurement("part1", 50), measurement("part2", 10)];
+    let comparisons = bench::compare(&baseline, &current);
+    assert_eq!(comparisons.len(), 1);
+    assert_eq!(comparisons[0].phase, "part1");
+    assert!((comparisons[0].change() - 25.0).abs() < 1e-9);
+}
+
+#[test]
+fn bench_day() {
+    let day = runner::find_day(1).unwrap();
+    let measurements = bench::bench(day, "3   4\n4   3\n", &day.default_params(), None, 3).unwrap();
+    let phases: Vec<_> = measurements.iter().map(|m| m.phase.as_str()).collect();
+    assert_eq!(phases, ["parse", "part1", "part2"]);
+    assert!(measurements.iter().all(|m| m.iterations == 3));
+}
@@ -0,0 +1,273 @@
+//! The day 16 maze search beyond the puzzle's own rules.
+
+use aoc::{Answer, Point, Rng, Solution};
+use day16::generate;
+use day16::render;
+use day16::routes::Route;
+use day16::{Costs, Day16, Search};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+fn day16(maze: &str) -> Day16 {
+    Day16::parse(maze).unwrap()
+}
+
+fn read_input(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../day16")
+        .join(name);
+    fs::read_to_string(path).unwrap()
+}
+
+fn best_score(day: &Day16) -> Option<String> {
+    day.part1().into_answer().unwrap()
+}
+
+fn tiles(day: &Day16) -> Option<String> {
+    day.part2().into_answer().unwrap()
+}
+
+/// The start faces a wall, with the end behind it.
+const DEAD_END: &str = "\
+#####
+#E.S#
+#####
+";
+
+#[test]
+fn costs() {
+    let maze = day16(DEAD_END);
+    assert_eq!(best_score(&maze).as_deref(), Some("2002"));
+
+    let maze = maze.with_costs(Costs {
+        u_turn: Some(500),
+        ..Costs::REINDEER
+    });
+    assert_eq!(best_score(&maze).as_deref(), Some("502"));
+
+    let maze = maze.with_costs(Costs {
+        backward: Some(10),
+        ..Costs::REINDEER
+    });
+    assert_eq!(best_score(&maze).as_deref(), Some("20"));
+    assert_eq!(tiles(&maze).as_deref(), Some("3"));
+}
+
+#[test]
+fn turns_as_cheap_as_steps() {
+    let maze = day16(&read_input("test_input")).with_costs(Costs {
+        forward: 1,
+        turn: 0,
+        u_turn: None,
+        backward: None,
+    });
+    // the shortest route through the maze, however winding
+    assert_eq!(best_score(&maze).as_deref(), Some("28"));
+    assert_eq!(tiles(&maze).as_deref(), Some("37"));
+}
+
+#[test]
+fn render_best_paths() {
+    let maze = day16(&read_input("test_input"));
+    let map = maze.render().unwrap();
+    assert_eq!(
+        map.to_string(),
+        "\
+###############
+#.......#....E#
+#.#.###.#.###^#
+#.....#.#...#^#
+#.###.#####.#^#
+#.#.#.......#^#
+#.#.#####.###^#
+#..OO>>>>>>v#^#
+###O#^#####v#^#
+#OOO#^....#v#^#
+#O#O#^###.#v#^#
+#>>>>^#...#v#^#
+#^###.#.#.#v#^#
+#S..#.....#>>^#
+###############
+"
+    );
+    let marked = map.points().filter(|&p| map[p] != '.' && map[p] != '#');
+    assert_eq!(marked.count(), 45);
+
+    let image = render::ppm(&map, 3);
+    let header = b"P6\n45 45\n255\n";
+    assert!(image.starts_with(header));
+    assert_eq!(image.len(), header.len() + 45 * 45 * 3);
+    // the bottom left pixel of the start tile, at (1, 13)
+    let pixel = header.len() + ((13 * 3 + 2) * 45 + 3) * 3;
+    assert_eq!(image[pixel..pixel + 3], [40, 170, 60]);
+}
+
+#[test]
+fn render_free_turns() {
+    let maze = day16("#####\n#..E#\n#S..#\n#####\n").with_costs(Costs {
+        forward: 1,
+        turn: 0,
+        u_turn: None,
+        backward: None,
+    });
+    // every state at a tile is as cheap as every other, so the path can't
+    // just follow the first best move back
+    let map = maze.render().unwrap();
+    assert_eq!(map.to_string(), "#####\n#>>E#\n#SOO#\n#####\n");
+}
+
+fn scores(routes: &[Route]) -> Vec<usize> {
+    routes.iter().map(|route| route.score).collect()
+}
+
+#[test]
+fn k_best_paths() {
+    let maze = day16(&read_input("test_input"));
+    let routes = maze.k_best_paths(8);
+    assert_eq!(
+        scores(&routes),
+        [7036, 7036, 7036, 9036, 9036, 9036, 9036, 9036]
+    );
+    let distinct: HashSet<_> = routes.iter().map(|route| &route.states).collect();
+    assert_eq!(distinct.len(), routes.len());
+    for route in &routes {
+        let (first, last) = (route.states[0], route.states[route.states.len() - 1]);
+        assert_eq!(first.position, Point::new(1, 13));
+        assert_eq!(last.position, Point::new(13, 1));
+        let states: HashSet<_> = route.states.iter().collect();
+        assert_eq!(states.len(), route.states.len(), "a route revisits a state");
+    }
+    let best_tiles: HashSet<_> = routes
+        .iter()
+        .filter(|route| route.score == 7036)
+        .flat_map(|route| route.states.iter().map(|state| state.position))
+        .collect();
+    assert_eq!(best_tiles.len(), 45);
+
+    let maze = day16(&read_input("test_input1"));
+    assert_eq!(
+        scores(&maze.k_best_paths(5)),
+        [11048, 11048, 11056, 11056, 12048]
+    );
+    assert!(day16(DEAD_END).k_best_paths(0).is_empty());
+    assert_eq!(scores(&day16(DEAD_END).k_best_paths(3)), [2002, 2002]);
+}
+
+/// Every spur search on the real maze, a few hundred per route, is guided
+/// by the costs left to `E`, or this takes half a minute.
+#[test]
+fn k_best_paths_input() {
+    let maze = day16(&read_input("input"));
+    assert_eq!(scores(&maze.k_best_paths(3)), [111480, 111480, 111480]);
+}
+
+/// Mazes too small to keep `S` and `E` apart are widened until they do.
+#[test]
+fn tiny_mazes() {
+    let mut rng = Rng::new(1);
+    for (width, height) in [(0, 0), (1, 1), (1, 2), (2, 1)] {
+        let maze = generate::maze(&mut rng, width, height, 0);
+        assert!(maze.contains('S') && maze.contains('E'), "{}", maze);
+        assert!(best_score(&day16(&maze)).is_some(), "{}", maze);
+    }
+}
+
+/// A* and Dijkstra's search find the same score and best tiles on random
+/// mazes, under every kind of costs.
+#[test]
+fn a_star_agrees() {
+    let mut rng = Rng::new(16);
+    let costs = [
+        Costs::REINDEER,
+        Costs {
+            forward: 1,
+            turn: 1,
+            u_turn: None,
+            backward: None,
+        },
+        Costs {
+            u_turn: Some(1500),
+            ..Costs::REINDEER
+        },
+        Costs {
+            backward: Some(3),
+            ..Costs::REINDEER
+        },
+        Costs {
+            forward: 2,
+            turn: 5,
+            u_turn: Some(3),
+            backward: Some(2),
+        },
+    ];
+    for _ in 0..40 {
+        let (width, height) = (1 + rng.below(8) as usize, 1 + rng.below(8) as usize);
+        let loops = *rng.pick(&[0, 10, 30, 60]);
+        let maze = day16(&generate::maze(&mut rng, width, height, loops));
+        for costs in costs {
+            let maze = maze.clone().with_costs(costs);
+            let a_star = maze.clone().with_search(Search::AStar);
+            let expected = (best_score(&maze), tiles(&maze));
+            assert!(expected.0.is_some());
+            assert_eq!(
+                (best_score(&a_star), tiles(&a_star)),
+                expected,
+                "{:?} on\n{}",
+                costs,
+                maze.render().unwrap()
+            );
+        }
+    }
+}
+
+/// A square room with no walls inside, `S` in the bottom left corner and `E`
+/// in the top right one.
+fn open_room(size: usize) -> String {
+    let mut rows = vec![format!("#{}#", ".".repeat(size)); size];
+    rows[0].replace_range(size..=size, "E");
+    rows[size - 1].replace_range(1..2, "S");
+    let wall = "#".repeat(size + 2);
+    format!("{}\n{}\n{}\n", wall, rows.join("\n"), wall)
+}
+
+/// Every monotone way across an open room is a best path when turns are
+/// free, which is far too many to walk one by one. Free turns also let the
+/// reindeer spin on the spot at no cost, so the predecessors form cycles.
+#[test]
+fn open_rooms() {
+    let free_turns = Costs {
+        forward: 1,
+        turn: 0,
+        u_turn: None,
+        backward: None,
+    };
+    for size in [2, 5, 150] {
+        let maze = day16(&open_room(size));
+        // along the bottom and up the right wall, the only way with one turn
+        assert_eq!(tiles(&maze), Some((2 * size - 1).to_string()));
+
+        let maze = maze.with_costs(free_turns);
+        assert_eq!(best_score(&maze), Some((2 * size - 2).to_string()));
+        assert_eq!(tiles(&maze), Some((size * size).to_string()));
+        let maze = maze.with_search(Search::AStar);
+        assert_eq!(tiles(&maze), Some((size * size).to_string()));
+        // one of them drawn over the rest, however the reindeer spun
+        let map = maze.render().unwrap();
+        let path = map.points().filter(|&p| "^v<>SE".contains(map[p]));
+        assert_eq!(path.count(), 2 * size - 1);
+    }
+    let maze = day16(&open_room(5)).with_costs(free_turns);
+    assert_eq!(
+        maze.render().unwrap().to_string(),
+        "\
+#######
+#>>>>E#
+#^OOOO#
+#^OOOO#
+#^OOOO#
+#SOOOO#
+#######
+"
+    );
+}
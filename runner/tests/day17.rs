@@ -0,0 +1,488 @@
+//! Tools for the day 17 computer, checked against its puzzle inputs and
+//! against each other on random programs.
+
+use aoc::{Answer, Part, Rng, Solution, SolveError};
+use day17::asm;
+use day17::compiled::Compiled;
+use day17::cpu::{RunError, CPU, DEFAULT_BUDGET};
+use day17::debugger::{format_registers, Debugger, Radix, Stop};
+use day17::decompile::decompile;
+use day17::generate::{loop_program, random_program};
+use day17::report::Report;
+use day17::symbolic;
+use day17::Day17;
+use std::fs;
+use std::path::PathBuf;
+
+/// The programs of every day 17 input file.
+fn programs() -> Vec<Vec<u64>> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../day17");
+    let mut programs = Vec::new();
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy();
+        if !name.starts_with("input") && !name.starts_with("test_input") {
+            continue;
+        }
+        let input = fs::read_to_string(&path).unwrap();
+        let program = input
+            .lines()
+            .find_map(|line| line.strip_prefix("Program: "))
+            .unwrap();
+        programs.push(asm::parse_program(&input, program).unwrap());
+    }
+    programs
+}
+
+#[test]
+fn disassemble() {
+    let program = asm::parse_program("0,3,5,4,3,0", "0,3,5,4,3,0").unwrap();
+    assert_eq!(asm::disassemble(&program), "adv 3\nout A\njnz 0\n");
+    assert_eq!(asm::disassemble(&[4, 6, 5]), "bxc 6\n5\n");
+}
+
+#[test]
+fn assemble() {
+    let text = "; loop until A is 0\nbst A\nbxl 6 ; literal operand\n\ncdv B\njnz 0\n";
+    assert_eq!(asm::assemble(text).unwrap(), [2, 4, 1, 6, 7, 5, 3, 0]);
+    assert!(asm::assemble("out D").is_err());
+    assert!(asm::assemble("mul 3").is_err());
+    assert!(asm::assemble("adv 8").is_err());
+}
+
+#[test]
+fn round_trip() {
+    for program in programs() {
+        let text = asm::disassemble(&program);
+        assert_eq!(asm::assemble(&text).unwrap(), program, "{}", text);
+    }
+}
+
+#[test]
+fn debugger() {
+    let program = asm::assemble("adv 3\nout A\njnz 0\n").unwrap();
+    let cpu = CPU {
+        A: 2024,
+        B: 0,
+        C: 0,
+        pc: 0,
+    };
+    let mut debugger = Debugger::new(cpu, &program);
+
+    let entry = debugger.step().unwrap().unwrap();
+    assert_eq!((entry.pc, entry.after.A, entry.output), (0, 253, None));
+    assert_eq!(
+        format_registers(&debugger.cpu, Radix::Octal),
+        "A=0o375 B=0o0 C=0o0 pc=2"
+    );
+
+    debugger.breakpoints.insert(4);
+    assert_eq!(debugger.resume(), Stop::Breakpoint(4));
+    assert_eq!(debugger.output, [5]);
+    debugger.breakpoints.clear();
+    debugger.break_on_output = true;
+    assert_eq!(debugger.resume(), Stop::Output(7));
+    debugger.break_on_output = false;
+    assert_eq!(debugger.resume(), Stop::Halted);
+    assert!(debugger.step().unwrap().is_none());
+
+    assert_eq!(debugger.output, [5, 7, 3, 0]);
+    assert_eq!(debugger.trace.len(), 12);
+    assert_eq!(
+        debugger.trace[1].to_string(),
+        "   2  out A  A=253 B=0 C=0 pc=4  out 5"
+    );
+}
+
+#[test]
+fn decompile_input() {
+    let program = asm::parse_program("", "2,4,1,6,7,5,4,4,1,7,0,3,5,5,3,0").unwrap();
+    assert_eq!(
+        decompile(&program).unwrap().to_string(),
+        "\
+do {
+    out((A ^ (A >> ((A & 7) ^ 6)) ^ 1) & 7)
+    (A, B, C) = (A >> 3, (A & 7) ^ (A >> ((A & 7) ^ 6)) ^ 1, A >> ((A & 7) ^ 6))
+} while A != 0
+"
+    );
+    assert_eq!(
+        decompile(&[0, 3, 5, 4, 3, 0]).unwrap().to_string(),
+        "do {\n    out((A >> 3) & 7)\n    A = A >> 3\n} while A != 0\n"
+    );
+    assert!(decompile(&[0, 1, 3, 0, 5, 4]).is_err());
+    assert!(decompile(&[5, 7]).is_err());
+}
+
+/// One pass over a program's body agrees with its decompiled expressions.
+#[test]
+fn decompile_matches_cpu() {
+    for program in programs() {
+        let decompiled = decompile(&program).unwrap();
+        let body = if decompiled.loops {
+            &program[..program.len() - 2]
+        } else {
+            &program[..]
+        };
+        for (a, b, c) in [(0, 0, 0), (2024, 0, 0), (37293246, 5, 9), (u64::MAX, 1, 2)] {
+            let mut cpu = CPU {
+                A: a,
+                B: b,
+                C: c,
+                pc: 0,
+            };
+            let mut output = Vec::new();
+            cpu.run(body, &mut output, DEFAULT_BUDGET).unwrap();
+            let expected: Vec<_> = decompiled.outputs.iter().map(|e| e.eval(a, b, c)).collect();
+            assert_eq!(output, expected);
+            assert_eq!(
+                (cpu.A, cpu.B, cpu.C),
+                (
+                    decompiled.a.eval(a, b, c),
+                    decompiled.b.eval(a, b, c),
+                    decompiled.c.eval(a, b, c)
+                )
+            );
+        }
+    }
+}
+
+fn run(a: u64, program: &str, budget: u64) -> Result<Vec<u64>, RunError> {
+    let program = asm::assemble(program).unwrap();
+    let mut cpu = CPU {
+        A: a,
+        B: 0,
+        C: 0,
+        pc: 0,
+    };
+    let mut output = Vec::new();
+    cpu.run(&program, &mut output, budget)?;
+    Ok(output)
+}
+
+#[test]
+fn run_errors() {
+    assert_eq!(
+        run(1, "bxl 1\njnz 0", DEFAULT_BUDGET),
+        Err(RunError::Loop { pc: 0 })
+    );
+    assert_eq!(
+        run(u64::MAX, "adv 1\njnz 0", 100),
+        Err(RunError::BudgetExhausted { budget: 100 })
+    );
+    assert_eq!(run(u64::MAX, "adv 1\njnz 0", 128), Ok(vec![]));
+    assert_eq!(
+        run(0, "out 2\nout 7", DEFAULT_BUDGET),
+        Err(RunError::InvalidComboOperand { pc: 2, operand: 7 })
+    );
+
+    let mut cpu = CPU {
+        A: 0,
+        B: 0,
+        C: 0,
+        pc: 0,
+    };
+    assert_eq!(
+        cpu.run(&[8, 0], &mut Vec::new(), DEFAULT_BUDGET),
+        Err(RunError::InvalidOpcode { pc: 0, opcode: 8 })
+    );
+}
+
+#[test]
+fn run_errors_reach_the_caller() {
+    let day = runner::find_day(17).unwrap();
+    let input = "Register A: 5\nRegister B: 0\nRegister C: 0\n\nProgram: 3,0\n";
+    for part in [Part::One, Part::Two] {
+        match (day.solve)(input, &day.default_params(), Some(part)) {
+            Err(SolveError::Part(failed, e)) => {
+                assert_eq!(failed, part);
+                assert_eq!(
+                    e.downcast_ref::<RunError>(),
+                    Some(&RunError::Loop { pc: 0 })
+                );
+            }
+            other => panic!("part {:?} should fail, got {:?}", part, other),
+        }
+    }
+}
+
+#[test]
+fn report() {
+    let program = asm::parse_program("", "0,1,5,4,3,0").unwrap();
+    let cpu = CPU {
+        A: 729,
+        B: 0,
+        C: 0,
+        pc: 0,
+    };
+    let report = Report::run(cpu, &program, DEFAULT_BUDGET);
+    assert_eq!(report.output, [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+    assert_eq!(report.executed, 30);
+    assert_eq!(report.opcodes, [10, 0, 0, 10, 0, 10, 0, 0]);
+    assert_eq!(report.hits, [10, 0, 10, 0, 10, 0]);
+    assert_eq!(
+        report.to_string(),
+        "output:    4,6,3,5,6,3,5,2,1,0\n\
+         registers: A=0 B=0 C=0 pc=6\n\
+         executed:  30 instructions\n\
+         opcodes:\n\
+         \x20 adv          10\n\
+         \x20 jnz          10\n\
+         \x20 out          10\n\
+         instructions:\n\
+         \x20    0  adv 1          10\n\
+         \x20    2  out A          10\n\
+         \x20    4  jnz 0          10\n"
+    );
+    assert_eq!(
+        report.json(),
+        "{\"output\":[4,6,3,5,6,3,5,2,1,0],\"a\":0,\"b\":0,\"c\":0,\"pc\":6,\"error\":null,\"executed\":30,\
+         \"opcodes\":{\"adv\":10,\"bxl\":0,\"bst\":0,\"jnz\":10,\"bxc\":0,\"out\":10,\"bdv\":0,\"cdv\":0},\
+         \"hits\":[10,0,10,0,10,0]}"
+    );
+
+    let report = Report::run(
+        CPU {
+            A: 0,
+            B: 0,
+            C: 0,
+            pc: 0,
+        },
+        &[5, 2, 5, 7],
+        DEFAULT_BUDGET,
+    );
+    assert_eq!(report.output, [2]);
+    assert_eq!(report.cpu.pc, 2);
+    assert_eq!(
+        report.error,
+        Some(RunError::InvalidComboOperand { pc: 2, operand: 7 })
+    );
+    assert_eq!(report.executed, 1);
+    assert!(report
+        .json()
+        .contains("\"error\":\"invalid combo operand 7 at 2\""));
+}
+
+#[test]
+fn compiled_matches_cpu() {
+    let mut programs = programs();
+    for text in [
+        "bxl 1\njnz 0",
+        "adv 1\njnz 0",
+        "out 2\nout 7",
+        "adv 3\njnz 1",
+    ] {
+        programs.push(asm::assemble(text).unwrap());
+    }
+    programs.push(vec![8, 0]);
+    let a_values: Vec<_> = (0..2000).chain([u64::MAX]).collect();
+    for program in programs {
+        let expected: Vec<_> = a_values
+            .iter()
+            .map(|&a| {
+                let mut cpu = CPU {
+                    A: a,
+                    B: 5,
+                    C: 3,
+                    pc: 0,
+                };
+                let mut output = Vec::new();
+                cpu.run(&program, &mut output, 1000).map(|()| output)
+            })
+            .collect();
+        let compiled = Compiled::new(&program);
+        for threads in [1, 4] {
+            assert_eq!(
+                compiled.run_batch(&a_values, 5, 3, 1000, threads),
+                expected,
+                "{:?} on {} threads",
+                program,
+                threads
+            );
+        }
+    }
+}
+
+#[test]
+fn symbolic_quines() {
+    let quine = asm::parse_program("", "0,3,5,4,3,0").unwrap();
+    assert_eq!(
+        symbolic::solve(&quine, 0, 0, &quine, 3).unwrap(),
+        [117440, 117441, 117442]
+    );
+
+    let program = asm::parse_program("", "2,4,1,6,7,5,4,4,1,7,0,3,5,5,3,0").unwrap();
+    let solutions = symbolic::solve(&program, 0, 0, &program, 10).unwrap();
+    assert_eq!(solutions, [47910079998866]);
+    for a in solutions {
+        assert_eq!(
+            run(a, &asm::disassemble(&program), DEFAULT_BUDGET),
+            Ok(program.clone())
+        );
+    }
+}
+
+#[test]
+fn symbolic_straight_line() {
+    let program = asm::assemble("bxl 3\nout B\nout A").unwrap();
+    assert_eq!(
+        symbolic::solve(&program, 1, 0, &[2, 5], 3).unwrap(),
+        [5, 13, 21]
+    );
+    assert!(symbolic::solve(&program, 1, 0, &[1, 5], 3)
+        .unwrap()
+        .is_empty());
+    assert!(symbolic::solve(&program, 1, 0, &[2], 3).unwrap().is_empty());
+    assert!(symbolic::solve(&[0, 1, 3, 0, 5, 4], 0, 0, &[0], 3).is_err());
+}
+
+/// Random programs checked per property. Each property has its own seed, so
+/// a failure can be reproduced by running its test alone.
+const RANDOM_PROGRAMS: usize = 300;
+
+/// A random value of A with a random number of bits.
+fn random_a(rng: &mut Rng) -> u64 {
+    rng.next_u64() >> rng.below(64)
+}
+
+fn output(program: &[u64], a: u64, budget: u64) -> Result<Vec<u64>, RunError> {
+    let mut cpu = CPU {
+        A: a,
+        B: 0,
+        C: 0,
+        pc: 0,
+    };
+    let mut output = Vec::new();
+    cpu.run(program, &mut output, budget)?;
+    Ok(output)
+}
+
+#[test]
+fn random_round_trip() {
+    let mut rng = Rng::new(1);
+    for _ in 0..RANDOM_PROGRAMS {
+        let instructions = rng.below(12) as usize;
+        let mut program = random_program(&mut rng, instructions);
+        if rng.chance(1, 4) {
+            program.push(rng.below(8));
+        }
+        let text = asm::disassemble(&program);
+        assert_eq!(asm::assemble(&text), Ok(program), "{}", text);
+    }
+}
+
+#[test]
+fn random_runs_agree() {
+    let mut rng = Rng::new(2);
+    let budget = 100;
+    for _ in 0..RANDOM_PROGRAMS {
+        let instructions = 1 + rng.below(10) as usize;
+        let program = random_program(&mut rng, instructions);
+        let compiled = Compiled::new(&program);
+        let (b, c) = (rng.below(1 << 20), rng.below(1 << 20));
+        let a_values: Vec<_> = (0..20).map(|_| random_a(&mut rng)).collect();
+        let batch = compiled.run_batch(&a_values, b, c, budget, 2);
+        for (&a, batched) in a_values.iter().zip(batch) {
+            let start = CPU {
+                A: a,
+                B: b,
+                C: c,
+                pc: 0,
+            };
+            let mut cpu = start.clone();
+            let mut output = Vec::new();
+            let result = cpu.run(&program, &mut output, budget);
+
+            let mut compiled_cpu = start.clone();
+            let mut compiled_output = Vec::new();
+            assert_eq!(
+                compiled.run(&mut compiled_cpu, &mut compiled_output, budget),
+                result,
+                "{:?} with A={}",
+                program,
+                a
+            );
+            assert_eq!(compiled_output, output);
+            assert_eq!(compiled_cpu, cpu);
+            assert_eq!(batched, result.map(|()| output.clone()));
+
+            let report = Report::run(start, &program, budget);
+            assert_eq!(report.error, result.err());
+            assert_eq!(report.output, output);
+            assert_eq!(report.cpu, cpu);
+        }
+    }
+}
+
+/// Running the decompiled loop gives the same output as the CPU.
+#[test]
+fn random_decompile() {
+    let mut rng = Rng::new(3);
+    for _ in 0..RANDOM_PROGRAMS {
+        let instructions = 1 + rng.below(8) as usize;
+        let program = loop_program(&mut rng, instructions);
+        let decompiled = decompile(&program).unwrap();
+        assert!(decompiled.loops);
+        for _ in 0..5 {
+            let a = random_a(&mut rng);
+            let (mut a_, mut b, mut c) = (a, 0, 0);
+            let mut expected = Vec::new();
+            loop {
+                expected.extend(decompiled.outputs.iter().map(|e| e.eval(a_, b, c)));
+                (a_, b, c) = (
+                    decompiled.a.eval(a_, b, c),
+                    decompiled.b.eval(a_, b, c),
+                    decompiled.c.eval(a_, b, c),
+                );
+                if a_ == 0 {
+                    break;
+                }
+            }
+            assert_eq!(
+                output(&program, a, DEFAULT_BUDGET),
+                Ok(expected),
+                "{}",
+                decompiled
+            );
+        }
+    }
+}
+
+/// The symbolic solver finds the same smallest A as a brute force search,
+/// and agrees with part 2 on which programs are quines.
+#[test]
+fn random_solvers_agree() {
+    let mut rng = Rng::new(4);
+    for _ in 0..RANDOM_PROGRAMS {
+        let instructions = 1 + rng.below(6) as usize;
+        let program = loop_program(&mut rng, instructions);
+        let a = rng.below(1 << 12);
+        let target = output(&program, a, DEFAULT_BUDGET).unwrap();
+
+        let solutions = symbolic::solve(&program, 0, 0, &target, 3).unwrap();
+        assert!(solutions.is_sorted(), "{:?}", solutions);
+        for &solution in &solutions {
+            assert_eq!(
+                output(&program, solution, DEFAULT_BUDGET),
+                Ok(target.clone())
+            );
+        }
+        let a_values: Vec<_> = (0..=a).collect();
+        let smallest = Compiled::new(&program)
+            .run_batch(&a_values, 0, 0, DEFAULT_BUDGET, 1)
+            .iter()
+            .position(|output| output.as_ref() == Ok(&target))
+            .map(|a| a as u64);
+        assert_eq!(solutions.first().copied(), smallest, "{:?}", program);
+
+        let input = format!(
+            "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+            asm::format_program(&program)
+        );
+        let quine = symbolic::solve(&program, 0, 0, &program, 1)
+            .ok()
+            .and_then(|solutions| solutions.first().copied());
+        let part2 = Day17::parse(&input).unwrap().part2().into_answer().unwrap();
+        assert_eq!(part2, quine.map(|a| a.to_string()), "{:?}", program);
+    }
+}
@@ -0,0 +1,47 @@
+use aoc::{Answers, Timings};
+use std::time::Duration;
+
+#[test]
+fn answers() {
+    let answers = Answers {
+        part1: Some(String::from("-42")),
+        part2: Some(String::from("6,1 \"x\"")),
+        timings: Timings {
+            parse: Duration::from_nanos(5),
+            part1: Some(Duration::from_micros(1)),
+            part2: None,
+        },
+        diagnostics: None,
+    };
+    assert_eq!(
+        runner::json::answers(18, &answers),
+        r#"{"day":18,"part1":-42,"part2":"6,1 \"x\"","timings":{"parse_ns":5,"part1_ns":1000,"part2_ns":null}}"#
+    );
+}
+
+#[test]
+fn numbers_only_as_json_writes_them() {
+    let answers = Answers {
+        part1: Some(String::from("007")),
+        part2: Some(String::from("+5")),
+        ..Answers::default()
+    };
+    assert!(runner::json::answers(1, &answers)
+        .starts_with(r#"{"day":1,"part1":"007","part2":"+5","timings":"#));
+
+    let answers = Answers {
+        part1: Some(String::from("18446744073709551615")),
+        part2: Some(String::from("-12")),
+        ..Answers::default()
+    };
+    assert!(runner::json::answers(1, &answers)
+        .starts_with(r#"{"day":1,"part1":18446744073709551615,"part2":-12,"timings":"#));
+}
+
+#[test]
+fn day_output() {
+    let day = runner::find_day(1).unwrap();
+    let answers = (day.solve)("3   4\n4   3\n", &day.default_params(), None).unwrap();
+    let json = runner::json::answers(day.number, &answers);
+    assert!(json.starts_with(r#"{"day":1,"part1":0,"part2":7,"timings":{"parse_ns":"#));
+}
@@ -0,0 +1,46 @@
+use aoc::{ParseError, SolveError};
+
+fn parse_error(day: u8, input: &str) -> ParseError {
+    let day = runner::find_day(day).expect("day not registered");
+    match (day.solve)(input, &day.default_params(), None) {
+        Err(SolveError::Parse(e)) => e,
+        Err(e) => panic!("input should fail to parse: {}", e),
+        Ok(_) => panic!("input should be rejected"),
+    }
+}
+
+#[test]
+fn reports_line_and_column() {
+    let e = parse_error(7, "190: 10 19\n3267: 81 4o 27\n");
+    assert_eq!((e.line, e.column), (2, 10));
+    assert_eq!(e.text, "4o");
+    assert_eq!(e.expected, "an operand");
+}
+
+#[test]
+fn reports_nested_fields_within_the_input() {
+    let e = parse_error(14, "p=0,4 v=3,-3\np=6,3 v=-1,q3\n");
+    assert_eq!((e.line, e.column), (2, 12));
+    assert_eq!(e.text, "q3");
+}
+
+#[test]
+fn reports_invalid_tiles() {
+    let e = parse_error(6, "....#.....\n...^..x...\n");
+    assert_eq!((e.line, e.column), (2, 7));
+    assert_eq!(e.text, "x");
+}
+
+#[test]
+fn reports_ragged_rows() {
+    let e = parse_error(16, "#####\n#S.E#\n###\n");
+    assert_eq!((e.line, e.column), (3, 1));
+    assert_eq!(e.expected, "a row of 5 tiles");
+}
+
+#[test]
+fn reports_missing_sections() {
+    let e = parse_error(5, "47|53\n97|13\n");
+    assert_eq!((e.line, e.column), (3, 1));
+    assert_eq!(e.text, "");
+}